@@ -0,0 +1,204 @@
+//! An Output for ANSI terminals.
+
+use std::collections::HashMap;
+use std::io::Write;
+
+use crate::{Narration, Object, Output, OutputBuilder, Viewer};
+
+/// Maps style names to ANSI SGR parameters, like "bold" to "1"
+/// and "npc" to "1;33".
+#[derive(Clone, Debug, Default)]
+pub struct Theme {
+    styles: HashMap<String, String>,
+}
+
+impl Theme {
+    /// A Theme without any styles.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A Theme for the `style::STANDARD` styles.
+    pub fn standard() -> Self {
+        Self::new()
+            .with("bold", "1")
+            .with("dim", "2")
+            .with("italic", "3")
+            .with("underline", "4")
+            .with("black", "30")
+            .with("red", "31")
+            .with("green", "32")
+            .with("yellow", "33")
+            .with("blue", "34")
+            .with("magenta", "35")
+            .with("cyan", "36")
+            .with("white", "37")
+            .with("item", "36")
+            .with("npc", "1;33")
+            .with("player", "1;32")
+            .with("speech", "35")
+            .with("exit", "1;34")
+            .with("room", "1")
+    }
+
+    /// Adds or replaces the SGR parameters used for style.
+    pub fn with(mut self, style: &str, sgr: &str) -> Self {
+        self.set(style, sgr);
+        self
+    }
+
+    /// Adds or replaces the SGR parameters used for style.
+    pub fn set(&mut self, style: &str, sgr: &str) {
+        self.styles.insert(String::from(style), String::from(sgr));
+    }
+
+    /// The SGR parameters used for style, if any.
+    pub fn get(&self, style: &str) -> Option<&str> {
+        self.styles.get(style).map(String::as_str)
+    }
+}
+
+/// An Output that writes to an `io::Write`, with styles
+/// translated to ANSI escape sequences by a Theme.
+///
+/// An empty style ends the latest style, unknown styles are not
+/// written but are ended like the others. Each text given to `done()`
+/// ends with a reset, if any style was used, and a newline.
+/// Control characters in the text, like ESC, are not written.
+/// The Viewer methods are answered by the viewer given to `new`.
+///
+/// Write errors are ignored, the Output can't report them.
+pub struct AnsiOutput<W: Write, V: Viewer> {
+    writer: W,
+    viewer: V,
+    theme: Theme,
+    // The SGR codes of the started styles, None for unknown styles.
+    styles: Vec<Option<String>>,
+    styled: bool,
+    narration: Narration,
+    contractions: bool,
+}
+
+impl<W: Write, V: Viewer> AnsiOutput<W, V> {
+    /// Creates an AnsiOutput using `Theme::standard()`.
+    pub fn new(writer: W, viewer: V) -> Self {
+        Self::with_theme(writer, viewer, Theme::standard())
+    }
+
+    pub fn with_theme(writer: W, viewer: V, theme: Theme) -> Self {
+        Self {
+            writer,
+            viewer,
+            theme,
+            styles: vec![],
+            styled: false,
+            narration: Narration::Second,
+            contractions: false,
+        }
+    }
+
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    pub fn theme_mut(&mut self) -> &mut Theme {
+        &mut self.theme
+    }
+
+    pub fn viewer(&self) -> &V {
+        &self.viewer
+    }
+
+    /// Sets if contractions are used, "he's" instead of "he is".
+    pub fn with_contractions(mut self, on: bool) -> Self {
+        self.contractions = on;
+        self
+    }
+
+    /// Sets how the viewer is referred to, "I", "you" or by name.
+    pub fn with_narration(mut self, narration: Narration) -> Self {
+        self.narration = narration;
+        self
+    }
+
+    /// Returns the writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn reset(&mut self) {
+        if self.styled {
+            let _ = self.writer.write_all(b"\x1b[0m");
+            self.styled = false;
+        }
+    }
+}
+
+impl<W: Write, V: Viewer> Output for AnsiOutput<W, V> {
+    /// Writes text without its control characters, except newlines
+    /// and tabs, so the text can't change the terminal.
+    fn write_text(&mut self, text: &str) {
+        let is_control = |c: char| c.is_ascii_control() && c != '\n' && c != '\t';
+        for part in text.split(is_control) {
+            let _ = self.writer.write_all(part.as_bytes());
+        }
+    }
+
+    /// Writes the escape sequence for style. An empty style ends the
+    /// latest style by resetting the text and writing the styles that
+    /// are still active, as ANSI terminals can't end just one style.
+    fn write_style(&mut self, style: &str) {
+        if !style.is_empty() {
+            let sgr = self.theme.get(style).map(String::from);
+            if let Some(sgr) = &sgr {
+                let _ = write!(self.writer, "\x1b[{}m", sgr);
+                self.styled = true;
+            }
+            self.styles.push(sgr);
+        } else if let Some(Some(_)) = self.styles.pop() {
+            self.reset();
+            for sgr in self.styles.iter().flatten() {
+                let _ = write!(self.writer, "\x1b[{}m", sgr);
+                self.styled = true;
+            }
+        }
+    }
+
+    /// Resets the style and ends the line.
+    fn done(&mut self) {
+        self.styles.clear();
+        self.reset();
+        let _ = self.writer.write_all(b"\n");
+        let _ = self.writer.flush();
+    }
+
+    fn out(&mut self) -> OutputBuilder<'_> {
+        OutputBuilder::new(self as &mut dyn Output)
+    }
+
+    fn contractions(&self) -> bool {
+        self.contractions
+    }
+
+    fn narration(&self) -> Narration {
+        self.narration
+    }
+}
+
+impl<W: Write, V: Viewer> Viewer for AnsiOutput<W, V> {
+    fn can_see(&self, who: &dyn Object) -> bool {
+        self.viewer.can_see(who)
+    }
+
+    fn can(&self, verb: &str, who: &dyn Object) -> bool {
+        self.viewer.can(verb, who)
+    }
+
+    fn has(&self, property: &str) -> bool {
+        self.viewer.has(property)
+    }
+
+    fn is_me(&self, who: &dyn Object) -> bool {
+        self.viewer.is_me(who)
+    }
+}
@@ -0,0 +1,174 @@
+//! Rendering one event for many viewers, like the `act()` of many MUDs.
+
+use crate::templates::{Context, Template};
+use crate::{Object, Output, Viewer};
+
+/// Renders template with ctx to each of the outputs.
+///
+/// Each viewer gets their own text, "You give Eva an apple." for the
+/// giver and "Adam gives you an apple." for Eva.
+pub fn broadcast<'o, I>(template: &dyn Template, ctx: &dyn Context, outputs: I)
+where
+    I: IntoIterator<Item = &'o mut dyn Output>,
+{
+    for out in outputs {
+        template.render(ctx, out);
+    }
+}
+
+/// Renders the template that variant picks for each of the outputs.
+///
+/// Outputs that variant returns None for are skipped,
+/// like viewers that shouldn't notice the event at all.
+pub fn broadcast_variants<'o, 't, I, F>(ctx: &dyn Context, outputs: I, mut variant: F)
+where
+    I: IntoIterator<Item = &'o mut dyn Output>,
+    F: FnMut(&dyn Output) -> Option<&'t dyn Template>,
+{
+    for out in outputs {
+        if let Some(template) = variant(out) {
+            template.render(ctx, out);
+        }
+    }
+}
+
+/// The part a viewer has in an event.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Role {
+    /// The viewer does it.
+    Actor,
+    /// It is done to the viewer.
+    Target,
+    /// The viewer is in the audience.
+    Other,
+}
+
+impl Role {
+    /// The role of viewer in an event where actor does something to target.
+    /// If target is the actor, the viewer is the Actor.
+    pub fn of<V>(viewer: &V, actor: &dyn Object, target: Option<&dyn Object>) -> Self
+    where
+        V: Viewer + ?Sized,
+    {
+        if viewer.is_me(actor) {
+            Role::Actor
+        } else if target.is_some_and(|target| viewer.is_me(target)) {
+            Role::Target
+        } else {
+            Role::Other
+        }
+    }
+}
+
+/// The templates used by `act`, like "You hug Eva.",
+/// "Adam hugs you." and "Adam hugs Eva.".
+///
+/// Roles without a template are skipped.
+#[derive(Clone, Copy, Default)]
+pub struct Act<'t> {
+    actor: Option<&'t dyn Template>,
+    target: Option<&'t dyn Template>,
+    others: Option<&'t dyn Template>,
+    reflexive: Option<&'t dyn Template>,
+}
+
+impl<'t> Act<'t> {
+    /// An Act with templates for the actor, the target and the others.
+    pub fn new(
+        actor: &'t dyn Template,
+        target: &'t dyn Template,
+        others: &'t dyn Template,
+    ) -> Self {
+        Self {
+            actor: Some(actor),
+            target: Some(target),
+            others: Some(others),
+            reflexive: None,
+        }
+    }
+
+    /// The template for the actor.
+    pub fn actor(mut self, template: &'t dyn Template) -> Self {
+        self.actor = Some(template);
+        self
+    }
+
+    /// The template for the target.
+    pub fn target(mut self, template: &'t dyn Template) -> Self {
+        self.target = Some(template);
+        self
+    }
+
+    /// The template for everyone else.
+    pub fn others(mut self, template: &'t dyn Template) -> Self {
+        self.others = Some(template);
+        self
+    }
+
+    /// The template used for everyone when the actor is the target,
+    /// "\The(me) hug\s(me) \himself(me)." gives "You hug yourself."
+    /// and "Adam hugs himself.".
+    /// Without it, act returns an error when the actor is the target.
+    pub fn reflexive(mut self, template: &'t dyn Template) -> Self {
+        self.reflexive = Some(template);
+        self
+    }
+
+    /// The template for a viewer with role.
+    pub fn get(&self, role: Role, is_reflexive: bool) -> Option<&'t dyn Template> {
+        if is_reflexive && self.reflexive.is_some() {
+            return self.reflexive;
+        }
+        match role {
+            Role::Actor => self.actor,
+            Role::Target => self.target,
+            Role::Other => self.others,
+        }
+    }
+}
+
+/// Sends the text of act to each of the outputs, the actor's text to the
+/// actor, the target's text to the target and the others' to the rest.
+/// target is None for events without one.
+///
+/// Returns an error, and sends nothing, if the actor is the target
+/// and act has no reflexive template, as "You hug you." would be sent.
+pub fn act<'o, I>(
+    act: &Act<'_>,
+    ctx: &dyn Context,
+    actor: &dyn Object,
+    target: Option<&dyn Object>,
+    outputs: I,
+) -> Result<(), String>
+where
+    I: IntoIterator<Item = &'o mut dyn Output>,
+{
+    let is_reflexive = target.is_some_and(|target| target.is_same(actor));
+    if is_reflexive && act.reflexive.is_none() {
+        return Err(String::from(
+            "The actor is the target, but there is no reflexive template",
+        ));
+    }
+    act_with(ctx, actor, target, outputs, |role, _| {
+        act.get(role, is_reflexive)
+    });
+    Ok(())
+}
+
+/// Like `act`, but variant picks the template for each role,
+/// it is also given the output so it can ask the viewer.
+/// Outputs that variant returns None for are skipped.
+pub fn act_with<'o, 't, I, F>(
+    ctx: &dyn Context,
+    actor: &dyn Object,
+    target: Option<&dyn Object>,
+    outputs: I,
+    mut variant: F,
+) where
+    I: IntoIterator<Item = &'o mut dyn Output>,
+    F: FnMut(Role, &dyn Output) -> Option<&'t dyn Template>,
+{
+    broadcast_variants(ctx, outputs, |out| {
+        variant(Role::of(out, actor, target), out)
+    });
+}
@@ -0,0 +1,287 @@
+//! Sentence plans, clauses that are built as data and realized as text.
+//!
+//! ```
+//! # use langgen::ansi::{AnsiOutput, Theme};
+//! # use langgen::clause::{Clause, Noun};
+//! # use langgen::{Gender, Named, Object, Viewer};
+//! # struct Thing(u64, &'static str, &'static str, Gender, bool);
+//! # impl Named for Thing {
+//! #     fn gender(&self) -> Gender { self.3 }
+//! #     fn is_thing(&self) -> bool { self.4 }
+//! #     fn is_short_proper(&self) -> bool { !self.4 }
+//! #     fn short_name(&self) -> &str { self.1 }
+//! #     fn is_long_proper(&self) -> bool { !self.4 }
+//! #     fn long_name(&self) -> &str { self.2 }
+//! #     fn is_short_plural_proper(&self) -> bool { !self.4 }
+//! #     fn short_plural_name(&self) -> &str { self.1 }
+//! #     fn is_long_plural_proper(&self) -> bool { !self.4 }
+//! #     fn long_plural_name(&self) -> &str { self.2 }
+//! # }
+//! # impl Object for Thing {
+//! #     fn id(&self) -> u64 { self.0 }
+//! # }
+//! # /// Sees everything but Eva, and is the object with id `me`.
+//! # struct Viewpoint { me: u64 }
+//! # impl Viewer for Viewpoint {
+//! #     fn can_see(&self, who: &dyn Object) -> bool { self.me == 0 || who.id() != 3 }
+//! #     fn can(&self, _verb: &str, who: &dyn Object) -> bool { self.can_see(who) }
+//! #     fn has(&self, _property: &str) -> bool { false }
+//! #     fn is_me(&self, who: &dyn Object) -> bool { who.id() == self.me }
+//! # }
+//! # let adam = Thing(1, "Adam", "Adam", Gender::Male, false);
+//! # let apple = Thing(2, "apple", "green apple", Gender::Neuter, true);
+//! # let eva = Thing(3, "Eva", "Eva Adamsfru", Gender::Female, false);
+//! let clause = Clause::new(Noun::the(&adam), "give")
+//!     .object(Noun::a(&apple))
+//!     .prep("to", Noun::the_(&eva));
+//!
+//! let mut out = AnsiOutput::with_theme(vec![], Viewpoint { me: 0 }, Theme::new());
+//! clause.render(&mut out);
+//! let mut adams_out = AnsiOutput::with_theme(vec![], Viewpoint { me: 1 }, Theme::new());
+//! clause.render(&mut adams_out);
+//!
+//! assert_eq!(out.into_inner(), b"Adam gives an apple to Eva Adamsfru.\n");
+//! assert_eq!(adams_out.into_inner(), b"You give an apple to someone.\n");
+//! ```
+//! The same clause is rendered differently depending on who is viewing it.
+
+use crate::verb::{self, Tense};
+use crate::{Object, Output, OutputBuilder};
+
+/// How a noun is introduced.
+#[derive(Copy, Clone)]
+pub enum Determiner<'a> {
+    /// "the apple"
+    The,
+    /// "an apple"
+    A,
+    /// "his apple", the owner is given.
+    Possessive(&'a dyn Object),
+}
+
+/// An object in a clause with its determiner.
+#[derive(Copy, Clone)]
+pub struct Noun<'a> {
+    obj: &'a dyn Object,
+    determiner: Determiner<'a>,
+    long: bool,
+}
+
+impl<'a> Noun<'a> {
+    /// "the apple"
+    pub fn the(obj: &'a dyn Object) -> Self {
+        Self {
+            obj,
+            determiner: Determiner::The,
+            long: false,
+        }
+    }
+
+    /// "the green apple"
+    pub fn the_(obj: &'a dyn Object) -> Self {
+        Self::the(obj).long()
+    }
+
+    /// "an apple"
+    pub fn a(obj: &'a dyn Object) -> Self {
+        Self {
+            obj,
+            determiner: Determiner::A,
+            long: false,
+        }
+    }
+
+    /// "a green apple"
+    pub fn a_(obj: &'a dyn Object) -> Self {
+        Self::a(obj).long()
+    }
+
+    /// "his apple"
+    pub fn my(owner: &'a dyn Object, obj: &'a dyn Object) -> Self {
+        Self {
+            obj,
+            determiner: Determiner::Possessive(owner),
+            long: false,
+        }
+    }
+
+    /// "his green apple"
+    pub fn my_(owner: &'a dyn Object, obj: &'a dyn Object) -> Self {
+        Self::my(owner, obj).long()
+    }
+
+    /// Use the long name.
+    pub fn long(mut self) -> Self {
+        self.long = true;
+        self
+    }
+}
+
+/// Is the clause positive or negated?
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Polarity {
+    /// "Adam sees you"
+    Positive,
+    /// "Adam doesn't see you"
+    Negative,
+}
+
+/// The kind of clause.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Mood {
+    /// "Adam gives you an apple."
+    Declarative,
+    /// "Does Adam give you an apple?"
+    Interrogative,
+    /// "Give Adam an apple." The subject is not written.
+    Imperative,
+}
+
+/// A clause with a subject, a verb, objects, prepositional phrases
+/// and adverbs.
+///
+/// The realizer takes care of agreement, articles, pronouns, reflexives,
+/// negation and word order for the viewer of the Output.
+pub struct Clause<'a> {
+    subject: Noun<'a>,
+    verb: String,
+    tense: Option<Tense>,
+    polarity: Polarity,
+    mood: Mood,
+    objects: Vec<Noun<'a>>,
+    preps: Vec<(String, Noun<'a>)>,
+    adverbs: Vec<String>,
+}
+
+impl<'a> Clause<'a> {
+    /// A positive, declarative clause in the Output's tense.
+    pub fn new(subject: Noun<'a>, verb: &str) -> Self {
+        Self {
+            subject,
+            verb: String::from(verb),
+            tense: None,
+            polarity: Polarity::Positive,
+            mood: Mood::Declarative,
+            objects: vec![],
+            preps: vec![],
+            adverbs: vec![],
+        }
+    }
+
+    /// Use tense instead of the Output's tense.
+    pub fn tense(mut self, tense: Tense) -> Self {
+        self.tense = Some(tense);
+        self
+    }
+
+    pub fn polarity(mut self, polarity: Polarity) -> Self {
+        self.polarity = polarity;
+        self
+    }
+
+    /// Same as `polarity(Polarity::Negative)`.
+    pub fn negated(self) -> Self {
+        self.polarity(Polarity::Negative)
+    }
+
+    pub fn mood(mut self, mood: Mood) -> Self {
+        self.mood = mood;
+        self
+    }
+
+    /// Adds an object after the verb. Indirect objects
+    /// should be added before direct objects, "gives Eva an apple".
+    pub fn object(mut self, object: Noun<'a>) -> Self {
+        self.objects.push(object);
+        self
+    }
+
+    /// Adds a prepositional phrase, "to Eva".
+    pub fn prep(mut self, preposition: &str, object: Noun<'a>) -> Self {
+        self.preps.push((String::from(preposition), object));
+        self
+    }
+
+    /// Adds an adverb at the end of the clause, "quickly".
+    pub fn adverb(mut self, adverb: &str) -> Self {
+        self.adverbs.push(String::from(adverb));
+        self
+    }
+
+    /// Writes the clause to the OutputBuilder.
+    pub fn realize<'o>(&self, mut ob: OutputBuilder<'o>) -> OutputBuilder<'o> {
+        if let Some(tense) = self.tense {
+            ob = ob.tense(tense);
+        }
+        let subject = self.subject.obj;
+        let negated = self.polarity == Polarity::Negative;
+        ob = match self.mood {
+            Mood::Declarative => {
+                let person = ob.person(subject);
+                let verb = if negated {
+                    verb::negate(&self.verb, person, ob.tense, ob.contract)
+                } else {
+                    verb::conjugate(&self.verb, person, ob.tense)
+                };
+                self.add_noun(ob, &self.subject, false).add_verb(&verb)
+            }
+            Mood::Interrogative => {
+                let (aux, main) = ob.question_parts(subject, &self.verb, negated);
+                ob.question = true;
+                ob = ob.s(&aux);
+                ob.subject = Some(true);
+                ob = self.add_noun(ob, &self.subject, false);
+                ob.subject = Some(false);
+                if main.is_empty() {
+                    ob
+                } else {
+                    ob.s(&main)
+                }
+            }
+            Mood::Imperative => {
+                if negated {
+                    let dont = verb::negate_auxiliary("do", ob.contract);
+                    ob = ob.s(&dont);
+                }
+                ob.add_verb(&self.verb)
+            }
+        };
+        for object in &self.objects {
+            ob = self.add_noun(ob, object, true);
+        }
+        for (preposition, object) in &self.preps {
+            ob = self.add_noun(ob.s(preposition), object, true);
+        }
+        for adverb in &self.adverbs {
+            ob = ob.s(adverb);
+        }
+        ob
+    }
+
+    /// Writes the clause as a sentence to the Output.
+    pub fn render(&self, out: &mut dyn Output) {
+        self.realize(out.out());
+    }
+
+    /// Writes noun, "himself" if it is the subject
+    /// and reflexive is true.
+    fn add_noun<'o>(
+        &self,
+        ob: OutputBuilder<'o>,
+        noun: &Noun<'_>,
+        reflexive: bool,
+    ) -> OutputBuilder<'o> {
+        let obj = noun.obj;
+        if reflexive && obj.is_same(self.subject.obj) {
+            return ob.himself(&obj);
+        }
+        match (noun.determiner, noun.long) {
+            (Determiner::The, false) => ob.the(&obj),
+            (Determiner::The, true) => ob.the_(&obj),
+            (Determiner::A, false) => ob.a(&obj),
+            (Determiner::A, true) => ob.a_(&obj),
+            (Determiner::Possessive(owner), false) => ob.my(&owner, &obj),
+            (Determiner::Possessive(owner), true) => ob.my_(&owner, &obj),
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use crate::{Gender, Named, Object};
+
+/// A crowd or collective of objects, like "three orcs",
+/// "the goblins" or "a pack of wolves".
+///
+/// The members keep their identity, so the viewer's `can_see` and
+/// `is_me` are asked about each member. If the viewer can't see all
+/// of them, the members are listed instead of using the group's name.
+/// Verbs and pronouns agree with the group as a plural.
+pub struct ObjectGroup<'a> {
+    id: u64,
+    members: Vec<&'a dyn Object>,
+    name: Option<Box<dyn Named + 'a>>,
+    short_name: String,
+    long_name: String,
+}
+
+/// A group of objects used as one subject or object, like "Adam and Eva".
+///
+/// It is an ObjectGroup without a name, so OutputBuilder renders the members
+/// as a list, ie "you and the goblin", and verbs and pronouns agree
+/// with it as a plural.
+pub type Compound<'a> = ObjectGroup<'a>;
+
+fn join(names: &[&str]) -> String {
+    match names.split_last() {
+        None => String::new(),
+        Some((last, [])) => String::from(*last),
+        Some((last, rest)) => format!("{} and {}", rest.join(", "), last),
+    }
+}
+
+/// Creates an id from the members' ids.
+fn combined_id(members: &[&dyn Object]) -> u64 {
+    let mut id: u64 = 0xcbf2_9ce4_8422_2325;
+    for m in members {
+        id ^= m.id();
+        id = id.wrapping_mul(0x0100_0000_01b3);
+    }
+    id
+}
+
+/// The plural names of an Object, used as the name of a group of it.
+struct PluralName<'a>(&'a dyn Object);
+
+impl<'a> Named for PluralName<'a> {
+    fn gender(&self) -> Gender {
+        Gender::Plural
+    }
+
+    fn is_thing(&self) -> bool {
+        self.0.is_thing()
+    }
+
+    fn is_short_proper(&self) -> bool {
+        self.0.is_short_plural_proper()
+    }
+
+    fn short_name(&self) -> &str {
+        self.0.short_plural_name()
+    }
+
+    fn is_long_proper(&self) -> bool {
+        self.0.is_long_plural_proper()
+    }
+
+    fn long_name(&self) -> &str {
+        self.0.long_plural_name()
+    }
+
+    fn is_short_plural_proper(&self) -> bool {
+        self.0.is_short_plural_proper()
+    }
+
+    fn short_plural_name(&self) -> &str {
+        self.0.short_plural_name()
+    }
+
+    fn is_long_plural_proper(&self) -> bool {
+        self.0.is_long_plural_proper()
+    }
+
+    fn long_plural_name(&self) -> &str {
+        self.0.long_plural_name()
+    }
+}
+
+impl<'a> ObjectGroup<'a> {
+    /// Creates a group of different members, None if there are no members.
+    /// It is rendered like "Adam, two orcs and a wolf".
+    /// Its id is derived from the members' ids.
+    pub fn new(members: &[&'a dyn Object]) -> Option<Self> {
+        if members.is_empty() {
+            return None;
+        }
+        let short_names: Vec<&str> = members.iter().map(|m| m.short_name()).collect();
+        let long_names: Vec<&str> = members.iter().map(|m| m.long_name()).collect();
+        Some(Self {
+            id: combined_id(members),
+            members: members.to_vec(),
+            name: None,
+            short_name: join(&short_names),
+            long_name: join(&long_names),
+        })
+    }
+
+    /// Creates a group of count of member, None if count is zero.
+    /// It is rendered like "three orcs" or "the three orcs".
+    pub fn counted(count: usize, member: &'a dyn Object) -> Option<Self> {
+        Self::new(&vec![member; count])
+    }
+
+    /// Creates a group of count of member without showing the count,
+    /// None if count is zero.
+    /// It is rendered like "some orcs" or "the orcs".
+    pub fn plural(count: usize, member: &'a dyn Object) -> Option<Self> {
+        Some(Self::counted(count, member)?.with_name(Box::new(PluralName(member))))
+    }
+
+    /// Sets the name of the group as a whole, ie "pack of wolves".
+    /// The name's gender decides the article, while verbs and
+    /// pronouns still agree with the group as a plural.
+    pub fn with_name(mut self, name: Box<dyn Named + 'a>) -> Self {
+        self.short_name = String::from(name.short_name());
+        self.long_name = String::from(name.long_name());
+        self.name = Some(name);
+        self
+    }
+
+    /// The number of members in the group.
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Is the group empty?
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+}
+
+impl<'a> Named for ObjectGroup<'a> {
+    fn gender(&self) -> Gender {
+        match self.members.as_slice() {
+            [member] => member.gender(),
+            _ => Gender::Plural,
+        }
+    }
+
+    fn is_thing(&self) -> bool {
+        self.members.iter().all(|m| m.is_thing())
+    }
+
+    fn is_short_proper(&self) -> bool {
+        self.name.as_ref().is_some_and(|n| n.is_short_proper())
+    }
+
+    fn short_name(&self) -> &str {
+        &self.short_name
+    }
+
+    fn is_long_proper(&self) -> bool {
+        self.name.as_ref().is_some_and(|n| n.is_long_proper())
+    }
+
+    fn long_name(&self) -> &str {
+        &self.long_name
+    }
+
+    fn is_short_plural_proper(&self) -> bool {
+        self.is_short_proper()
+    }
+
+    fn short_plural_name(&self) -> &str {
+        &self.short_name
+    }
+
+    fn is_long_plural_proper(&self) -> bool {
+        self.is_long_proper()
+    }
+
+    fn long_plural_name(&self) -> &str {
+        &self.long_name
+    }
+}
+
+impl<'a> Object for ObjectGroup<'a> {
+    fn id(&self) -> u64 {
+        self.id
+    }
+
+    fn members(&self) -> &[&dyn Object] {
+        &self.members
+    }
+
+    fn group_name(&self) -> Option<&dyn Named> {
+        self.name.as_deref().map(|n| n as &dyn Named)
+    }
+}
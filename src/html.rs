@@ -0,0 +1,228 @@
+//! An Output for HTML clients.
+
+use std::collections::HashMap;
+use std::io::Write;
+
+use crate::style::STANDARD;
+use crate::{Narration, Object, Output, OutputBuilder, Viewer};
+
+/// Maps style names to CSS classes, like "npc" to "npc"
+/// or "bold" to "text-bold".
+#[derive(Clone, Debug, Default)]
+pub struct Classes {
+    classes: HashMap<String, String>,
+}
+
+impl Classes {
+    /// Classes without any styles.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Classes for the `style::STANDARD` styles,
+    /// each style uses the class with the same name.
+    pub fn standard() -> Self {
+        let mut classes = Self::new();
+        for style in STANDARD {
+            classes.set(style, style);
+        }
+        classes
+    }
+
+    /// Adds or replaces the CSS class used for style.
+    pub fn with(mut self, style: &str, class: &str) -> Self {
+        self.set(style, class);
+        self
+    }
+
+    /// Adds or replaces the CSS class used for style.
+    pub fn set(&mut self, style: &str, class: &str) {
+        self.classes
+            .insert(String::from(style), String::from(class));
+    }
+
+    /// The CSS class used for style, if any.
+    pub fn get(&self, style: &str) -> Option<&str> {
+        self.classes.get(style).map(String::as_str)
+    }
+}
+
+/// Escapes text so it can be used in HTML text and attributes.
+pub fn escape(text: &str) -> String {
+    let mut ret = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => ret.push_str("&amp;"),
+            '<' => ret.push_str("&lt;"),
+            '>' => ret.push_str("&gt;"),
+            '"' => ret.push_str("&quot;"),
+            '\'' => ret.push_str("&#39;"),
+            c => ret.push(c),
+        }
+    }
+    ret
+}
+
+/// An Output that writes HTML to an `io::Write`.
+///
+/// All text is escaped. Each text given to `done()` is written as
+/// a `<p>` element. A style starts a `<span>` with the style's CSS class
+/// and an empty style ends the latest started style. Styles without a
+/// class are not written, but still have to be ended.
+/// Styles that are not ended are closed by `done()`.
+/// The Viewer methods are answered by the viewer given to `new`.
+///
+/// Write errors are ignored, the Output can't report them.
+pub struct HtmlOutput<W: Write, V: Viewer> {
+    writer: W,
+    viewer: V,
+    classes: Classes,
+    in_block: bool,
+    // Is a span written for each started style?
+    open: Vec<bool>,
+    narration: Narration,
+    contractions: bool,
+}
+
+impl<W: Write, V: Viewer> HtmlOutput<W, V> {
+    /// Creates an HtmlOutput using `Classes::standard()`.
+    pub fn new(writer: W, viewer: V) -> Self {
+        Self::with_classes(writer, viewer, Classes::standard())
+    }
+
+    pub fn with_classes(writer: W, viewer: V, classes: Classes) -> Self {
+        Self {
+            writer,
+            viewer,
+            classes,
+            in_block: false,
+            open: vec![],
+            narration: Narration::Second,
+            contractions: false,
+        }
+    }
+
+    pub fn classes(&self) -> &Classes {
+        &self.classes
+    }
+
+    pub fn classes_mut(&mut self) -> &mut Classes {
+        &mut self.classes
+    }
+
+    pub fn viewer(&self) -> &V {
+        &self.viewer
+    }
+
+    /// Sets if contractions are used, "he's" instead of "he is".
+    pub fn with_contractions(mut self, on: bool) -> Self {
+        self.contractions = on;
+        self
+    }
+
+    /// Sets how the viewer is referred to, "I", "you" or by name.
+    pub fn with_narration(mut self, narration: Narration) -> Self {
+        self.narration = narration;
+        self
+    }
+
+    /// Returns the writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn start_block(&mut self) {
+        if !self.in_block {
+            let _ = self.writer.write_all(b"<p>");
+            self.in_block = true;
+        }
+    }
+
+    fn end_style(&mut self) {
+        if let Some(true) = self.open.pop() {
+            let _ = self.writer.write_all(b"</span>");
+        }
+    }
+}
+
+impl<W: Write, V: Viewer> Output for HtmlOutput<W, V> {
+    fn write_text(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        self.start_block();
+        let _ = self.writer.write_all(escape(text).as_bytes());
+    }
+
+    /// Starts a span for style, or ends the latest if style is empty.
+    fn write_style(&mut self, style: &str) {
+        if style.is_empty() {
+            self.end_style();
+            return;
+        }
+        self.start_block();
+        match self.classes.get(style) {
+            Some(class) => {
+                let _ = write!(self.writer, "<span class=\"{}\">", escape(class));
+                self.open.push(true);
+            }
+            None => self.open.push(false),
+        }
+    }
+
+    /// Ends all styles and the block.
+    fn done(&mut self) {
+        while !self.open.is_empty() {
+            self.end_style();
+        }
+        if self.in_block {
+            let _ = self.writer.write_all(b"</p>\n");
+            self.in_block = false;
+        }
+        let _ = self.writer.flush();
+    }
+
+    fn out(&mut self) -> OutputBuilder<'_> {
+        OutputBuilder::new(self as &mut dyn Output)
+    }
+
+    fn contractions(&self) -> bool {
+        self.contractions
+    }
+
+    fn narration(&self) -> Narration {
+        self.narration
+    }
+}
+
+impl<W: Write, V: Viewer> Viewer for HtmlOutput<W, V> {
+    fn can_see(&self, who: &dyn Object) -> bool {
+        self.viewer.can_see(who)
+    }
+
+    fn can(&self, verb: &str, who: &dyn Object) -> bool {
+        self.viewer.can(verb, who)
+    }
+
+    fn has(&self, property: &str) -> bool {
+        self.viewer.has(property)
+    }
+
+    fn is_me(&self, who: &dyn Object) -> bool {
+        self.viewer.is_me(who)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("plain text"), "plain text");
+        assert_eq!(
+            escape("<b onclick=\"x\">Tom & Jerry's</b>"),
+            "&lt;b onclick=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/b&gt;"
+        );
+    }
+}
@@ -0,0 +1,167 @@
+//! An Output for rich clients, that want to know what the text is about.
+
+use std::io::Write;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Mention, Narration, Object, Output, OutputBuilder, Viewer};
+
+/// A part of an Event's text, with the same styles and Object.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Segment {
+    pub text: String,
+    /// The active styles, the innermost last.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub styles: Vec<String>,
+    /// The Object the text refers to, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub object: Option<Mention>,
+}
+
+/// The text given to one `done()`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Event {
+    /// All of the text, without styles.
+    pub text: String,
+    pub segments: Vec<Segment>,
+}
+
+/// An Output that writes each text given to `done()` as an Event,
+/// a JSON object on its own line, to an `io::Write`.
+///
+/// The text is split into Segments, so a client can style it
+/// and link the mentioned Objects.
+/// An empty style ends the latest started style.
+/// The Viewer methods are answered by the viewer given to `new`.
+///
+/// Write errors are ignored, the Output can't report them.
+pub struct JsonOutput<W: Write, V: Viewer> {
+    writer: W,
+    viewer: V,
+    event: Event,
+    styles: Vec<String>,
+    objects: Vec<Mention>,
+    narration: Narration,
+    contractions: bool,
+}
+
+impl<W: Write, V: Viewer> JsonOutput<W, V> {
+    pub fn new(writer: W, viewer: V) -> Self {
+        Self {
+            writer,
+            viewer,
+            event: Event::default(),
+            styles: vec![],
+            objects: vec![],
+            narration: Narration::Second,
+            contractions: false,
+        }
+    }
+
+    pub fn viewer(&self) -> &V {
+        &self.viewer
+    }
+
+    /// Sets if contractions are used, "he's" instead of "he is".
+    pub fn with_contractions(mut self, on: bool) -> Self {
+        self.contractions = on;
+        self
+    }
+
+    /// Sets how the viewer is referred to, "I", "you" or by name.
+    pub fn with_narration(mut self, narration: Narration) -> Self {
+        self.narration = narration;
+        self
+    }
+
+    /// Returns the writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write, V: Viewer> Output for JsonOutput<W, V> {
+    fn write_text(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        self.event.text.push_str(text);
+        let object = self.objects.last().copied();
+        if let Some(last) = self.event.segments.last_mut() {
+            if last.styles == self.styles && last.object == object {
+                last.text.push_str(text);
+                return;
+            }
+        }
+        self.event.segments.push(Segment {
+            text: String::from(text),
+            styles: self.styles.clone(),
+            object,
+        });
+    }
+
+    /// Starts style, or ends the latest if style is empty.
+    fn write_style(&mut self, style: &str) {
+        if style.is_empty() {
+            self.styles.pop();
+        } else {
+            self.styles.push(String::from(style));
+        }
+    }
+
+    fn end_style(&mut self, _style: &str, active: &[String]) {
+        self.styles = active.to_vec();
+    }
+
+    fn begin_object(&mut self, mention: &Mention) {
+        self.objects.push(*mention);
+    }
+
+    fn end_object(&mut self) {
+        self.objects.pop();
+    }
+
+    /// Writes the Event, if there was any text.
+    fn done(&mut self) {
+        let event = std::mem::take(&mut self.event);
+        self.styles.clear();
+        self.objects.clear();
+        if event.segments.is_empty() {
+            return;
+        }
+        if serde_json::to_writer(&mut self.writer, &event).is_ok() {
+            let _ = self.writer.write_all(b"\n");
+        }
+        let _ = self.writer.flush();
+    }
+
+    fn out(&mut self) -> OutputBuilder<'_> {
+        OutputBuilder::new(self as &mut dyn Output)
+    }
+
+    fn contractions(&self) -> bool {
+        self.contractions
+    }
+
+    fn narration(&self) -> Narration {
+        self.narration
+    }
+}
+
+impl<W: Write, V: Viewer> Viewer for JsonOutput<W, V> {
+    fn can_see(&self, who: &dyn Object) -> bool {
+        self.viewer.can_see(who)
+    }
+
+    fn can(&self, verb: &str, who: &dyn Object) -> bool {
+        self.viewer.can(verb, who)
+    }
+
+    fn has(&self, property: &str) -> bool {
+        self.viewer.has(property)
+    }
+
+    fn is_me(&self, who: &dyn Object) -> bool {
+        self.viewer.is_me(who)
+    }
+}
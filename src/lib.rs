@@ -1,9 +1,23 @@
+pub mod ansi;
+pub mod broadcast;
+pub mod clause;
+mod group;
+pub mod html;
+#[cfg(feature = "json")]
+pub mod json;
 pub mod named;
+pub mod parser;
+pub mod sense;
+pub mod social;
+pub mod style;
 mod suffix;
 pub mod templates;
+pub mod verb;
+pub mod wrap;
 
 /// The gender of Named:s.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Gender {
     Male,
     Female,
@@ -28,6 +42,17 @@ pub trait Named {
     fn short_plural_name(&self) -> &str;
     fn is_long_plural_proper(&self) -> bool;
     fn long_plural_name(&self) -> &str;
+
+    /// Other names the Named can be referred to by.
+    fn aliases(&self) -> &[String] {
+        &[]
+    }
+
+    /// What the Named is called when it can't be seen.
+    /// If None, "someone" or "something" is used.
+    fn unseen_name(&self) -> Option<&str> {
+        None
+    }
 }
 
 /**
@@ -49,7 +74,96 @@ pub trait Viewer {
 }
 
 /// An Object is an object or subject in templates.
-pub trait Object: Named {}
+pub trait Object: Named {
+    /// A stable identifier for the Object.
+    /// Two Objects with the same id are the same object,
+    /// even if they are different wrappers or clones.
+    fn id(&self) -> u64;
+
+    /// Is other the same object as self?
+    fn is_same(&self, other: &dyn Object) -> bool {
+        self.id() == other.id()
+    }
+
+    /// The members if the Object is a group of objects, like "Adam and Eva".
+    /// The members are rendered instead of the Object's own names.
+    fn members(&self) -> &[&dyn Object] {
+        &[]
+    }
+
+    /// The name of a group as a whole, like "pack of wolves" or "goblins".
+    /// It is used instead of listing the members when the viewer
+    /// can see all of them.
+    fn group_name(&self) -> Option<&dyn Named> {
+        None
+    }
+}
+
+/// References to Named:s are also Named, so a `&dyn Object`
+/// can be given to the generic methods of OutputBuilder.
+impl<T: Named + ?Sized> Named for &T {
+    fn gender(&self) -> Gender {
+        (**self).gender()
+    }
+
+    fn is_thing(&self) -> bool {
+        (**self).is_thing()
+    }
+
+    fn is_short_proper(&self) -> bool {
+        (**self).is_short_proper()
+    }
+
+    fn short_name(&self) -> &str {
+        (**self).short_name()
+    }
+
+    fn is_long_proper(&self) -> bool {
+        (**self).is_long_proper()
+    }
+
+    fn long_name(&self) -> &str {
+        (**self).long_name()
+    }
+
+    fn is_short_plural_proper(&self) -> bool {
+        (**self).is_short_plural_proper()
+    }
+
+    fn short_plural_name(&self) -> &str {
+        (**self).short_plural_name()
+    }
+
+    fn is_long_plural_proper(&self) -> bool {
+        (**self).is_long_plural_proper()
+    }
+
+    fn long_plural_name(&self) -> &str {
+        (**self).long_plural_name()
+    }
+
+    fn aliases(&self) -> &[String] {
+        (**self).aliases()
+    }
+
+    fn unseen_name(&self) -> Option<&str> {
+        (**self).unseen_name()
+    }
+}
+
+impl<T: Object + ?Sized> Object for &T {
+    fn id(&self) -> u64 {
+        (**self).id()
+    }
+
+    fn members(&self) -> &[&dyn Object] {
+        (**self).members()
+    }
+
+    fn group_name(&self) -> Option<&dyn Named> {
+        (**self).group_name()
+    }
+}
 
 /**
  * The Output trait is used for objects that templates
@@ -57,21 +171,58 @@ pub trait Object: Named {}
  */
 pub trait Output: Viewer {
     fn write_text(&mut self, text: &str);
+    /// Writes style, an empty style ends the latest style.
     fn write_style(&mut self, style: &str);
     fn done(&mut self);
     fn out(&mut self) -> OutputBuilder<'_>;
+
+    /// Style is started, after the styles that are already active.
+    /// Calls `write_style(style)`.
+    fn begin_style(&mut self, style: &str) {
+        self.write_style(style);
+    }
+
+    /// Style ends, active are the styles that are still active,
+    /// the innermost last.
+    /// Calls `write_style("")`.
+    fn end_style(&mut self, _style: &str, _active: &[String]) {
+        self.write_style("");
+    }
+
+    /// The text until `end_object()` refers to the mentioned Object.
+    /// Does nothing.
+    fn begin_object(&mut self, _mention: &Mention) {}
+
+    /// The text about the mentioned Object ends.
+    /// Does nothing.
+    fn end_object(&mut self) {}
+
+    /// The tense verbs are written in, unless
+    /// `OutputBuilder::tense` is used.
+    fn tense(&self) -> verb::Tense {
+        verb::Tense::Present
+    }
+
+    /// Should contractions be used, "he's" and "doesn't" instead
+    /// of "he is" and "does not"?
+    fn contractions(&self) -> bool {
+        false
+    }
+
+    /// How the viewer is referred to, unless
+    /// `OutputBuilder::narration` is used.
+    fn narration(&self) -> Narration {
+        Narration::Second
+    }
 }
 
 fn last_char(s: &str) -> Option<char> {
-    s.chars().rev().next()
+    s.chars().next_back()
 }
 
 fn needs_dot(s: &str) -> bool {
     if let Some(c) = last_char(s) {
-        match c {
-            '.' | '?' | '!' | ':' | ';' | '"' => false,
-            _ => true,
-        }
+        !matches!(c, '.' | '?' | '!' | ':' | ';' | '"')
     } else {
         false
     }
@@ -86,6 +237,43 @@ fn is_vowel(c: char) -> bool {
     }
 }
 
+// Used for counted plurals, "three apples".
+fn number_word(n: usize) -> String {
+    const ONES: [&str; 20] = [
+        "zero",
+        "one",
+        "two",
+        "three",
+        "four",
+        "five",
+        "six",
+        "seven",
+        "eight",
+        "nine",
+        "ten",
+        "eleven",
+        "twelve",
+        "thirteen",
+        "fourteen",
+        "fifteen",
+        "sixteen",
+        "seventeen",
+        "eighteen",
+        "nineteen",
+    ];
+    const TENS: [&str; 10] = [
+        "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+    ];
+    match n {
+        0..=19 => String::from(ONES[n]),
+        20..=99 => match n % 10 {
+            0 => String::from(TENS[n / 10]),
+            ones => format!("{}-{}", TENS[n / 10], ONES[ones]),
+        },
+        _ => n.to_string(),
+    }
+}
+
 fn uppercase_first_char(s: &str, to: &mut String) {
     let mut c = s.chars();
     if let Some(ch) = c.next() {
@@ -96,6 +284,75 @@ fn uppercase_first_char(s: &str, to: &mut String) {
     }
 }
 
+pub use crate::group::{Compound, ObjectGroup};
+
+/// The kind of pronoun used for an Object.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Pronoun {
+    You,
+    YouPlural,
+    I,
+    We,
+    Male,
+    Female,
+    Neuter,
+    Plural,
+}
+
+impl Pronoun {
+    /// Picks the word for this pronoun from
+    /// [you, you (plural), I, we, male, female, neuter, plural].
+    fn pick(self, words: [&'static str; 8]) -> &'static str {
+        words[self as usize]
+    }
+}
+
+/// The possessive pronouns, "your/my/his...".
+const POSSESSIVE: [&str; 8] = ["your", "your", "my", "our", "his", "her", "its", "their"];
+
+/// Which name, or other word, was used for a mentioned Object.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum NameForm {
+    ShortName,
+    LongName,
+    ShortPluralName,
+    LongPluralName,
+    /// The name of an ObjectGroup, "a pack of wolves".
+    GroupName,
+    /// you/he/his/himself etc.
+    Pronoun,
+    /// someone/something or the unseen name.
+    Unseen,
+}
+
+/// An Object mentioned in the text, see `Output::begin_object`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Mention {
+    /// The Object's id.
+    pub id: u64,
+    pub form: NameForm,
+    /// Did the viewer see the Object?
+    pub seen: bool,
+}
+
+/// How the viewer of an Output is referred to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Narration {
+    /// "I give the apple to Gandalf.", for diaries and personal logs.
+    First,
+    /// "You give the apple to Gandalf.", the default.
+    Second,
+    /// "Adam gives the apple to Gandalf.", the viewer is
+    /// referred to by name, for admin logs and transcripts.
+    Third,
+}
+
 /// An Output that just throws away the text.
 pub struct NullOutput {}
 
@@ -134,21 +391,68 @@ impl Viewer for NullOutput {
     }
 }
 
+/// A part of the text in an OutputBuilder, written in order.
+enum Segment {
+    Text(String),
+    Style(String),
+    EndStyle,
+    Object(Mention),
+    EndObject,
+}
+
 /// `OutputBuilder` helps with the fluent interface for `Output::out()`
 pub struct OutputBuilder<'a> {
     o: &'a mut dyn Output,
+    // The text and styles before s.
+    segments: Vec<Segment>,
     s: String,
     cap_it: bool,
     add_space: bool,
     suppress_dot: bool,
+    oxford_comma: bool,
+    tense: verb::Tense,
+    contract: bool,
+    question: bool,
+    narration: Narration,
+    // Is the viewer the subject ("I") or an object ("me")?
+    // It is Some(false) after a verb, until the clause ends.
+    // If None, it is decided by the words before it.
+    subject: Option<bool>,
+    // Was the last word a pronoun, so a verb can be contracted, "he's"?
+    after_pronoun: bool,
 }
 
+/// Punctuation that ends a clause, the next word can be its subject.
+const CLAUSE_ENDS: &[char] = &['.', '!', '?', ';', ':', '\'', '"'];
+
 /// Calles `Output::done()`.
 impl<'a> Drop for OutputBuilder<'a> {
     fn drop(&mut self) {
+        let mut styles: Vec<String> = vec![];
+        for segment in &self.segments {
+            match segment {
+                Segment::Text(text) => self.o.write_text(text),
+                Segment::Style(style) => {
+                    self.o.begin_style(style);
+                    styles.push(style.clone());
+                }
+                Segment::EndStyle => {
+                    // Ending a style that wasn't started is ignored.
+                    if let Some(style) = styles.pop() {
+                        self.o.end_style(&style, &styles);
+                    }
+                }
+                Segment::Object(mention) => self.o.begin_object(mention),
+                Segment::EndObject => self.o.end_object(),
+            }
+        }
         self.o.write_text(&self.s);
-        if !self.suppress_dot && needs_dot(&self.s) {
-            self.o.write_text(".");
+        // Styles that are not ended are ended before the dot.
+        while let Some(style) = styles.pop() {
+            self.o.end_style(&style, &styles);
+        }
+        if !self.suppress_dot && needs_dot(self.last_text()) {
+            self.o.write_text(if self.question { "?" } else { "." });
         }
         self.o.done();
     }
@@ -158,21 +462,263 @@ impl<'a> OutputBuilder<'a> {
     /// Creates a new OutputBuilder that will output
     /// the text to Output.
     pub fn new(o: &'a mut dyn Output) -> Self {
+        let tense = o.tense();
+        let contract = o.contractions();
+        let narration = o.narration();
         Self {
             o,
+            segments: vec![],
             s: String::new(),
             cap_it: true,
             add_space: false,
             suppress_dot: false,
+            oxford_comma: false,
+            tense,
+            contract,
+            question: false,
+            narration,
+            subject: None,
+            after_pronoun: false,
         }
     }
 
     /// Is this gender supposed to be treated as singular?
     fn is_singular(gender: Gender) -> bool {
-        match gender {
-            Gender::Plural | Gender::Uncountable => false,
-            _ => true,
+        !matches!(gender, Gender::Plural | Gender::Uncountable)
+    }
+
+    /// Can the viewer see who, or any of who's members?
+    fn is_seen(&self, who: &dyn Object) -> bool {
+        let members = who.members();
+        if members.is_empty() {
+            self.o.is_me(who) || self.o.can_see(who)
+        } else {
+            members.iter().any(|m| self.is_seen(*m))
+        }
+    }
+
+    /// Should who be referred to as "you" (or "I")?
+    fn is_me(&self, who: &dyn Object) -> bool {
+        self.narration != Narration::Third && self.o.is_me(who)
+    }
+
+    /// "you", "I" or "me" for the viewer.
+    fn me_word(&self) -> &'static str {
+        match self.narration {
+            Narration::First if self.subject.unwrap_or_else(|| self.is_subject_position()) => "I",
+            Narration::First => "me",
+            _ => "you",
+        }
+    }
+
+    /// Is the next word the subject of a clause? It is at the start
+    /// of the text, after a conjunction or after punctuation that ends
+    /// a clause, "I", "Eva and I" or "Eva smiles, but I".
+    /// Only used if no verb has been written in the clause.
+    fn is_subject_position(&self) -> bool {
+        const CONJUNCTIONS: &[&str] = &[
+            "and", "or", "but", "so", "yet", "nor", "then", "because", "while", "when", "if",
+            "although", "though", "unless",
+        ];
+        let word = std::iter::once(self.s.as_str())
+            .chain(self.segments.iter().rev().filter_map(|seg| match seg {
+                Segment::Text(text) => Some(text.as_str()),
+                _ => None,
+            }))
+            .find_map(|text| text.split_whitespace().last());
+        match word {
+            None => true,
+            Some(word) if word.ends_with(CLAUSE_ENDS) => true,
+            Some(word) => CONJUNCTIONS.contains(&word.to_lowercase().as_str()),
+        }
+    }
+
+    /// Which kind of pronoun should be used for who?
+    /// Groups with more than one member are always in plural.
+    fn pronoun(&self, who: &dyn Object) -> Pronoun {
+        let members = who.members();
+        if members.len() == 1 {
+            self.pronoun(members[0])
+        } else if !members.is_empty() {
+            if members.iter().any(|m| self.is_me(*m)) {
+                if self.narration == Narration::First {
+                    Pronoun::We
+                } else {
+                    Pronoun::YouPlural
+                }
+            } else {
+                Pronoun::Plural
+            }
+        } else if self.is_me(who) {
+            if self.narration == Narration::First {
+                Pronoun::I
+            } else {
+                Pronoun::You
+            }
+        } else if !self.o.can_see(who) {
+            if who.is_short_proper() {
+                Pronoun::Male
+            } else {
+                Pronoun::Neuter
+            }
+        } else {
+            match who.gender() {
+                Gender::Male => Pronoun::Male,
+                Gender::Female => Pronoun::Female,
+                Gender::Neuter | Gender::Uncountable => Pronoun::Neuter,
+                Gender::Plural => Pronoun::Plural,
+            }
+        }
+    }
+
+    fn add_pronoun<T>(self, who: &T, words: [&'static str; 8]) -> Self
+    where
+        T: Object,
+    {
+        let word = self.pronoun(who).pick(words);
+        self.mention(who, NameForm::Pronoun, |ob| ob.s(word))
+    }
+
+    /// Sends "you/he/she/it/they" to Output.
+    /// The text is capitalized as needed.
+    pub fn he<T: Object>(self, who: &T) -> Self {
+        let mut ob = self.add_pronoun(who, ["you", "you", "I", "we", "he", "she", "it", "they"]);
+        ob.after_pronoun = true;
+        ob
+    }
+
+    /// Sends "you are/he is/they are" to Output,
+    /// "you're/he's/they're" if contractions are used.
+    /// The text is capitalized as needed.
+    pub fn he_s<T: Object>(self, who: &T) -> Self {
+        self.he(who).is(who)
+    }
+
+    /// Sends "you have/he has/they have" to Output,
+    /// "you've/he's/they've" if contractions are used.
+    /// Meant for "have" as an auxiliary verb, "they've gone".
+    /// The text is capitalized as needed.
+    pub fn he_has<T: Object>(self, who: &T) -> Self {
+        let verb = verb::conjugate("have", self.person(who), self.tense.simple());
+        self.he(who).write_verb(&verb, true)
+    }
+
+    /// Sends "your/his/her/its/their" to Output.
+    /// The text is capitalized as needed.
+    pub fn his<T: Object>(self, who: &T) -> Self {
+        self.add_pronoun(who, POSSESSIVE)
+    }
+
+    /// Sends "yours/his/hers/its/theirs" to Output.
+    /// The text is capitalized as needed.
+    pub fn hiss<T: Object>(self, who: &T) -> Self {
+        self.add_pronoun(
+            who,
+            [
+                "yours", "yours", "mine", "ours", "his", "hers", "its", "theirs",
+            ],
+        )
+    }
+
+    /// Sends "you/him/her/it/them" to Output.
+    /// The text is capitalized as needed.
+    pub fn him<T: Object>(self, who: &T) -> Self {
+        self.add_pronoun(who, ["you", "you", "me", "us", "him", "her", "it", "them"])
+    }
+
+    /// Sends "yourself/himself/herself/itself/themselves" to Output.
+    /// The text is capitalized as needed.
+    pub fn himself<T: Object>(self, who: &T) -> Self {
+        self.add_pronoun(
+            who,
+            [
+                "yourself",
+                "yourselves",
+                "myself",
+                "ourselves",
+                "himself",
+                "herself",
+                "itself",
+                "themselves",
+            ],
+        )
+    }
+
+    /// Sends "yourself/himself/..." to Output if obj is the same
+    /// object as who, otherwise the() is used for obj.
+    /// The text is capitalized as needed.
+    pub fn the_or_himself<TW, TO>(self, who: &TW, obj: &TO) -> Self
+    where
+        TW: Object,
+        TO: Object,
+    {
+        if who.is_same(obj) {
+            self.himself(who)
+        } else {
+            self.the(obj)
+        }
+    }
+
+    /// The text after this is written in style,
+    /// until `end_style()` is called.
+    /// Styles can be nested, those that are not ended
+    /// are ended when the text is done.
+    pub fn style(mut self, style: &str) -> Self {
+        self.end_segment();
+        self.segments.push(Segment::Style(String::from(style)));
+        self
+    }
+
+    /// Ends the latest style started by `style()`.
+    pub fn end_style(mut self) -> Self {
+        self.end_segment();
+        self.segments.push(Segment::EndStyle);
+        self
+    }
+
+    /// Sends the text written by f as a mention of obj.
+    fn mention<F>(mut self, obj: &dyn Object, form: NameForm, f: F) -> Self
+    where
+        F: FnOnce(Self) -> Self,
+    {
+        // The space goes before the object's text.
+        self.add_pending_space();
+        self.end_segment();
+        let mention = Mention {
+            id: obj.id(),
+            form,
+            seen: self.is_seen(obj),
+        };
+        self.segments.push(Segment::Object(mention));
+        let mut ob = f(self);
+        ob.end_segment();
+        ob.segments.push(Segment::EndObject);
+        ob
+    }
+
+    fn end_segment(&mut self) {
+        if !self.s.is_empty() {
+            let text = std::mem::take(&mut self.s);
+            self.segments.push(Segment::Text(text));
+        }
+    }
+
+    /// Has any text been written?
+    fn has_text(&self) -> bool {
+        !self.last_text().is_empty()
+    }
+
+    /// The latest text segment that isn't empty.
+    fn last_text(&self) -> &str {
+        if !self.s.is_empty() {
+            return &self.s;
+        }
+        for segment in self.segments.iter().rev() {
+            if let Segment::Text(text) = segment {
+                return text;
+            }
         }
+        ""
     }
 
     /// The next thing that is output should not be capitalized.
@@ -187,12 +733,30 @@ impl<'a> OutputBuilder<'a> {
         self
     }
 
+    /// Writes the space that goes before the next word, if any.
+    fn add_pending_space(&mut self) {
+        if self.add_space && self.s.is_empty() {
+            // The space goes before any styles that were just started.
+            let pos = self
+                .segments
+                .iter()
+                .rposition(|seg| !matches!(seg, Segment::Style(_)))
+                .map_or(0, |pos| pos + 1);
+            self.segments.insert(pos, Segment::Text(String::from(" ")));
+        } else if self.add_space {
+            self.s.push(' ');
+        }
+        self.add_space = false;
+    }
+
     /// Send the text to the Output.
     /// The text is capitalized as needed.
     pub fn s(mut self, text: &str) -> Self {
-        if self.add_space {
-            self.s.push(' ');
+        self.after_pronoun = false;
+        if text.ends_with(CLAUSE_ENDS) {
+            self.subject = None;
         }
+        self.add_pending_space();
         self.add_space = true;
         if self.cap_it {
             self.cap_it = false;
@@ -204,235 +768,359 @@ impl<'a> OutputBuilder<'a> {
         }
     }
 
-    fn add_s(str: &mut String) {
-        let mut add: &str = "";
-        let mut uc = false;
-        let mut remove = 0;
+    /// Which person should verbs be conjugated in for who?
+    fn person(&self, who: &dyn Object) -> verb::Person {
+        match self.pronoun(who) {
+            Pronoun::You | Pronoun::YouPlural => verb::Person::Second,
+            Pronoun::I => verb::Person::First,
+            Pronoun::We | Pronoun::Plural => verb::Person::Plural,
+            _ => verb::Person::ThirdSingular,
+        }
+    }
 
-        {
-            let mut ci = str.chars().rev();
-            if let Some(ch) = ci.next() {
-                if ch.is_uppercase() {
-                    uc = true;
-                }
-                add = match ch {
-                    's' | 'o' | 'z' | 'x' | 'S' | 'O' | 'Z' | 'X' => "es",
-                    'y' | 'Y' => {
-                        remove = 1;
-                        if let Some(c2) = ci.next() {
-                            if is_vowel(c2) {
-                                remove = 0;
-                                "s"
-                            } else {
-                                "ies"
-                            }
-                        } else {
-                            "ies"
-                        }
-                    }
-                    'h' | 'H' => {
-                        if let Some(c2) = ci.next() {
-                            if c2 == 'c' || c2 == 's' || c2 == 'C' || c2 == 'S' {
-                                "es"
-                            } else {
-                                "s"
-                            }
-                        } else {
-                            "s"
-                        }
-                    }
-                    _ => "s",
-                }
+    /// The verbs that follow are written in tense,
+    /// "gave" instead of "gives".
+    pub fn tense(mut self, tense: verb::Tense) -> Self {
+        self.tense = tense;
+        self
+    }
+
+    /// Sends the conjugated verb to Output,
+    /// the following words are not the subject.
+    fn add_verb(self, verb: &str) -> Self {
+        self.write_verb(verb, false)
+    }
+
+    /// Writes the verb, contracted with the pronoun before it
+    /// if contractions are used. have/has/had are only contracted
+    /// if contract_have is true, "he's" is not used for "he has an apple".
+    fn write_verb(mut self, verb: &str, contract_have: bool) -> Self {
+        let mut verb = String::from(verb);
+        if self.contract && self.after_pronoun {
+            let contraction = verb::Contraction::Pronoun {
+                have: contract_have,
+            };
+            if let Some(contracted) = verb::contract(&verb, contraction) {
+                self.add_space = false;
+                verb = contracted;
             }
         }
-        while remove > 0 {
-            str.pop();
-            remove -= 1;
+        let mut ob = self.s(&verb);
+        ob.subject = Some(false);
+        ob
+    }
+
+    /// Send the verb to the Output, conjugated to agree with obj
+    /// and in the builder's tense.
+    /// Irregular verbs like "be" and "have" are handled.
+    /// The text is capitalized as needed.
+    pub fn v<T>(self, obj: &T, verb: &str) -> Self
+    where
+        T: Object,
+    {
+        let verb = verb::conjugate(verb, self.person(obj), self.tense);
+        self.add_verb(&verb)
+    }
+
+    /// How the viewer is referred to, "I", "you" or by name.
+    pub fn narration(mut self, narration: Narration) -> Self {
+        self.narration = narration;
+        self
+    }
+
+    /// Contractions are used, "he's" and "doesn't" instead of
+    /// "he is" and "does not", if on.
+    pub fn contractions(mut self, on: bool) -> Self {
+        self.contract = on;
+        self
+    }
+
+    /// Send the negated verb to the Output, conjugated to agree with obj,
+    /// "doesn't see" or "were not".
+    /// The text is capitalized as needed.
+    pub fn v_not<T>(self, obj: &T, verb: &str) -> Self
+    where
+        T: Object,
+    {
+        let verb = verb::negate(verb, self.person(obj), self.tense, self.contract);
+        self.add_verb(&verb)
+    }
+
+    /// Sends a question with who as subject to Output,
+    /// "does the goblin want" or "is Adam".
+    /// The text ends with '?' instead of '.'.
+    pub fn ask<T>(self, who: &T, verb: &str) -> Self
+    where
+        T: Object,
+    {
+        self.add_question(who, verb, false)
+    }
+
+    /// Sends a negated question with who as subject to Output,
+    /// "doesn't the goblin want" or "does the goblin not want".
+    /// The text ends with '?' instead of '.'.
+    pub fn ask_not<T>(self, who: &T, verb: &str) -> Self
+    where
+        T: Object,
+    {
+        self.add_question(who, verb, true)
+    }
+
+    /// Splits the verb for a question into what comes before and
+    /// after the subject, ("doesn't", "want") or ("does", "not want").
+    fn question_parts(&self, who: &dyn Object, verb: &str, negated: bool) -> (String, String) {
+        let (mut aux, mut main) = verb::split_auxiliary(verb, self.person(who), self.tense);
+        if negated && self.contract {
+            aux = verb::negate_auxiliary(&aux, true);
+        } else if negated {
+            main = if main.is_empty() {
+                String::from("not")
+            } else {
+                format!("not {}", main)
+            };
         }
-        if uc {
-            str.push_str(&add.to_uppercase());
+        (aux, main)
+    }
+
+    fn add_question(mut self, who: &dyn Object, verb: &str, negated: bool) -> Self {
+        let (aux, main) = self.question_parts(who, verb, negated);
+        self.question = true;
+        // The subject comes after the verb in questions, "do I want".
+        let mut ob = self.s(&aux);
+        ob.subject = Some(true);
+        ob = ob.the(&who);
+        ob.subject = Some(false);
+        if main.is_empty() {
+            ob
         } else {
-            str.push_str(add);
+            ob.s(&main)
         }
     }
 
+    /// Sends the present participle of verb to Output, "running".
+    /// The text is capitalized as needed.
+    pub fn ing(self, verb: &str) -> Self {
+        let verb = verb::present_participle(verb);
+        self.s(&verb)
+    }
+
+    /// Sends the past participle of verb to Output, "dropped" or "given".
+    /// The text is capitalized as needed.
+    pub fn ed(self, verb: &str) -> Self {
+        let verb = verb::past_participle(verb);
+        self.s(&verb)
+    }
+
     /// Send the verb to the Output.
     /// Appends 's' at the end of it, if needed.
-    pub fn v_e<T>(mut self, obj: &T, verb: &str) -> Self
+    /// It is the same as v().
+    pub fn v_e<T>(self, obj: &T, verb: &str) -> Self
     where
         T: Object,
     {
-        if self.cap_it {
-            unimplemented!();
+        self.v(obj, verb)
+    }
+
+    /// What obj is called when the viewer can't see it.
+    fn unseen_word<T: Object + ?Sized>(obj: &T, is_proper: bool) -> &str {
+        match obj.unseen_name() {
+            Some(name) => name,
+            None if is_proper => "someone",
+            None => "something",
         }
-        if self.add_space {
-            self.s.push(' ');
+    }
+
+    fn add_the_word(self, obj: &dyn Object, name: &str, is_proper: bool, form: NameForm) -> Self {
+        if self.is_me(obj) {
+            let word = self.me_word();
+            let mut ob = self.mention(obj, NameForm::Pronoun, |ob| ob.s(word));
+            ob.after_pronoun = true;
+            ob
+        } else if self.o.can_see(obj) {
+            self.mention(obj, form, |ob| ob.add_the_name(name, is_proper))
+        } else {
+            let word = Self::unseen_word(obj, is_proper);
+            self.mention(obj, NameForm::Unseen, |ob| ob.s(word))
         }
-        self.s.push_str(verb);
-        self.cap_it = false;
-        self.add_space = false;
-        if Self::is_singular(obj.gender()) && !self.o.is_me(obj) {
-            Self::add_s(&mut self.s);
+    }
+
+    fn add_the_name(mut self, name: &str, is_proper: bool) -> Self {
+        if !is_proper {
+            self = self.s("the");
         }
-        self.s("")
+        self.s(name)
     }
 
-    fn add_the_word<T>(mut self, obj: &T, name: &str, is_proper: bool) -> Self
-    where
-        T: Object,
-    {
-        if self.o.is_me(obj) {
-            self.s("you")
+    fn add_a_word(self, obj: &dyn Object, name: &str, is_prop: bool, form: NameForm) -> Self {
+        if self.is_me(obj) {
+            let word = self.me_word();
+            self.mention(obj, NameForm::Pronoun, |ob| ob.s(word))
         } else if self.o.can_see(obj) {
-            if !is_proper {
-                self = self.s("the");
-            }
-            self.s(name)
-        } else if is_proper {
-            self.s("someone")
+            let is_singular = Self::is_singular(obj.gender());
+            self.mention(obj, form, |ob| ob.add_a_name(name, is_prop, is_singular))
         } else {
-            self.s("something")
+            let word = Self::unseen_word(obj, is_prop);
+            self.mention(obj, NameForm::Unseen, |ob| ob.s(word))
         }
     }
 
-    fn add_a_word<T>(mut self, obj: &T, name: &str, is_prop: bool) -> Self
-    where
-        T: Object,
-    {
-        if self.o.is_me(obj) {
-            self.s("you")
-        } else if self.o.can_see(obj) {
-            if !is_prop && Self::is_singular(obj.gender()) {
-                let mut should_be_an = false;
-                if let Some(c) = name.chars().next() {
-                    if is_vowel(c) {
-                        should_be_an = true;
-                    }
+    fn add_a_name(mut self, name: &str, is_prop: bool, is_singular: bool) -> Self {
+        if !is_prop && is_singular {
+            let mut should_be_an = false;
+            if let Some(c) = name.chars().next() {
+                if is_vowel(c) {
+                    should_be_an = true;
                 }
-                self = if should_be_an {
-                    self.s("an")
+            }
+            self = if should_be_an {
+                self.s("an")
+            } else {
+                self.s("a")
+            }
+        } else if !is_prop {
+            self = self.s("some");
+        }
+        self.s(name)
+    }
+
+    /// Sends a group, either by its group name or by listing its members.
+    fn add_group(self, obj: &dyn Object, definite: bool, long: bool) -> Self {
+        let members = obj.members();
+        if let Some(named) = obj.group_name() {
+            if members
+                .iter()
+                .all(|m| self.o.can_see(*m) && !self.is_me(*m))
+            {
+                let (name, is_proper) = if long {
+                    (named.long_name(), named.is_long_proper())
                 } else {
-                    self.s("a")
-                }
-            } else if !is_prop {
-                self = self.s("some");
+                    (named.short_name(), named.is_short_proper())
+                };
+                let is_singular = Self::is_singular(named.gender());
+                return self.mention(obj, NameForm::GroupName, |ob| {
+                    if definite {
+                        ob.add_the_name(name, is_proper)
+                    } else {
+                        ob.add_a_name(name, is_proper, is_singular)
+                    }
+                });
             }
-            self.s(name)
-        } else if is_prop {
-            self.s("someone")
-        } else {
-            self.s("something")
         }
+        self.add_list(members, definite, long)
     }
 
     /// Sends "the object-short-name" to Output.
     /// If the viewer can't see it, someone/something is sent instead.
     /// The text is capitalized as needed.
     pub fn the<T: Object>(self, obj: &T) -> Self {
-        self.add_the_word(obj, obj.short_name(), obj.is_short_proper())
+        if !obj.members().is_empty() {
+            return self.add_group(obj, true, false);
+        }
+        self.add_the_word(
+            obj,
+            obj.short_name(),
+            obj.is_short_proper(),
+            NameForm::ShortName,
+        )
     }
 
     /// Sends "the object-long-name" to Output.
     /// If the viewer can't see it, someone/something is sent instead.
     /// The text is capitalized as needed.
     pub fn the_<T: Object>(self, obj: &T) -> Self {
-        self.add_the_word(obj, obj.long_name(), obj.is_long_proper())
+        if !obj.members().is_empty() {
+            return self.add_group(obj, true, true);
+        }
+        self.add_the_word(
+            obj,
+            obj.long_name(),
+            obj.is_long_proper(),
+            NameForm::LongName,
+        )
     }
 
     /// Sends "a/an object-short-name" to Output.
     /// If the viewer can't see it, someone/something is sent instead.
     /// The text is capitalized as needed.
     pub fn a<T: Object>(self, obj: &T) -> Self {
-        self.add_a_word(obj, obj.short_name(), obj.is_short_proper())
+        if !obj.members().is_empty() {
+            return self.add_group(obj, false, false);
+        }
+        self.add_a_word(
+            obj,
+            obj.short_name(),
+            obj.is_short_proper(),
+            NameForm::ShortName,
+        )
     }
 
     /// Sends "a/an object-long-name" to Output.
     /// If the viewer can't see it, someone/something is sent instead.
     /// The text is capitalized as needed.
     pub fn a_<T: Object>(self, obj: &T) -> Self {
-        self.add_a_word(obj, obj.long_name(), obj.is_long_proper())
+        if !obj.members().is_empty() {
+            return self.add_group(obj, false, true);
+        }
+        self.add_a_word(
+            obj,
+            obj.long_name(),
+            obj.is_long_proper(),
+            NameForm::LongName,
+        )
     }
 
     /// Sends "my/his/her/their/its object-short-name" to Output.
     /// If the viewer can't see it, a() is used instead.
     /// The text is capitalized as needed.
-    pub fn my<TW, TO>(mut self, who: &TW, obj: &TO) -> Self
+    pub fn my<TW, TO>(self, who: &TW, obj: &TO) -> Self
     where
         TW: Object,
         TO: Object,
     {
-        if self.o.is_me(who) {
-            self = self.s("your").s(obj.short_name());
-        } else if self.o.can_see(who) {
-            let pron = match who.gender() {
-                Gender::Male => "his",
-                Gender::Female => "her",
-                Gender::Plural => "their",
-                _ => "its",
-            };
-            self = self.s(pron).s(obj.short_name());
+        if self.is_seen(who) {
+            let pron = self.pronoun(who).pick(POSSESSIVE);
+            self.mention(who, NameForm::Pronoun, |ob| ob.s(pron))
+                .mention(obj, NameForm::ShortName, |ob| ob.s(obj.short_name()))
         } else {
-            self = self.a(obj);
+            self.a(obj)
         }
-        self
     }
 
     /// Sends "my/his/her/their/its object-long-name" to Output.
     /// If the viewer can't see it, a() is used instead.
     /// The text is capitalized as needed.
-    pub fn my_<TW, TO>(mut self, who: &TW, obj: &TO) -> Self
+    pub fn my_<TW, TO>(self, who: &TW, obj: &TO) -> Self
     where
         TW: Object,
         TO: Object,
     {
-        if self.o.is_me(who) {
-            self = self.s("your").s(obj.long_name());
-        } else if self.o.can_see(who) {
-            let pron = match who.gender() {
-                Gender::Male => "his",
-                Gender::Female => "her",
-                Gender::Plural => "their",
-                _ => "its",
-            };
-            self = self.s(pron).s(obj.long_name());
+        if self.is_seen(who) {
+            let pron = self.pronoun(who).pick(POSSESSIVE);
+            self.mention(who, NameForm::Pronoun, |ob| ob.s(pron))
+                .mention(obj, NameForm::LongName, |ob| ob.s(obj.long_name()))
         } else {
-            self = self.a_(obj);
+            self.a_(obj)
         }
-        self
     }
 
-    fn sing_plur<T>(self, who: &T, singular: &str, plural: &str) -> Self
-    where
-        T: Object,
-    {
-        let mut g = who.gender();
-        if self.o.is_me(who) {
-            g = Gender::Plural;
-        } else if !self.o.can_see(who) {
-            g = Gender::Male;
-        }
-        self.s(match g {
-            Gender::Plural => plural,
-            _ => singular,
-        })
-    }
-
-    /// Sends "is"/"are" to Output.
+    /// Sends "is"/"are" to Output, "was"/"were" in past tense.
     /// The text is capitalized as needed.
     pub fn is<T>(self, who: &T) -> Self
     where
         T: Object,
     {
-        self.sing_plur(who, "is", "are")
+        let verb = verb::conjugate("be", self.person(who), self.tense.simple());
+        self.add_verb(&verb)
     }
 
-    /// Sends "has"/"have" to Output.
+    /// Sends "has"/"have" to Output, "had" in past tense.
     /// The text is capitalized as needed.
     pub fn has<T>(self, who: &T) -> Self
     where
         T: Object,
     {
-        self.sing_plur(who, "has", "have")
+        let verb = verb::conjugate("have", self.person(who), self.tense.simple());
+        self.add_verb(&verb)
     }
 
     /// Sends "the <object-short-name>'s" to Output.
@@ -442,32 +1130,7 @@ impl<'a> OutputBuilder<'a> {
     where
         T: Object,
     {
-        if self.o.is_me(obj) {
-            self.s("your")
-        } else if self.o.can_see(obj) {
-            if let Some(ch) = obj.short_name().chars().rev().next() {
-                let uc = ch.is_uppercase();
-                let add = match ch {
-                    's' | 'S' => "'",
-                    _ => {
-                        if uc {
-                            "'S"
-                        } else {
-                            "'s"
-                        }
-                    }
-                };
-                let mut s2 = self.the(obj);
-                s2.add_space = false;
-                s2.s(add)
-            } else {
-                self
-            }
-        } else if obj.is_short_proper() {
-            self.s("someone's")
-        } else {
-            self.s("something's")
-        }
+        self.add_possessive(obj, false)
     }
 
     /// Sends "your"/"the <object-long-name>'s" to Output.
@@ -477,32 +1140,30 @@ impl<'a> OutputBuilder<'a> {
     where
         T: Object,
     {
-        if self.o.is_me(obj) {
-            self.s("your")
-        } else if self.o.can_see(obj) {
-            if let Some(ch) = obj.long_name().chars().rev().next() {
-                let uc = ch.is_uppercase();
-                let add = match ch {
-                    's' | 'S' => "'",
-                    _ => {
-                        if uc {
-                            "'S"
-                        } else {
-                            "'s"
-                        }
-                    }
-                };
-                let mut s2 = self.the_(obj);
-                s2.add_space = false;
-                s2.s(add)
-            } else {
-                self
-            }
-        } else if obj.is_short_proper() {
-            self.s("someone's")
-        } else {
-            self.s("something's")
+        self.add_possessive(obj, true)
+    }
+
+    fn add_possessive(self, obj: &dyn Object, long: bool) -> Self {
+        if self.is_me(obj) {
+            return self.his(&obj);
+        } else if !self.is_seen(obj) {
+            let word = Self::unseen_word(obj, obj.is_short_proper());
+            return self.s(&format!("{}'s", word));
         }
+        let mut ob = if long {
+            self.the_(&obj)
+        } else {
+            self.the(&obj)
+        };
+        // "the orcs'" but "the orc's", as it was written.
+        let add = match ob.last_text().chars().next_back() {
+            None => return ob,
+            Some('s' | 'S') => "'",
+            Some(ch) if ch.is_uppercase() => "'S",
+            Some(_) => "'s",
+        };
+        ob.add_space = false;
+        ob.s(add)
     }
 
     /// Sends "yours"/"the <object-short-name>'s" to Output.
@@ -545,6 +1206,135 @@ impl<'a> OutputBuilder<'a> {
         unimplemented!();
     }
 
+    /// Lists will have a comma before the final "and".
+    pub fn oxford_comma(mut self) -> Self {
+        self.oxford_comma = true;
+        self
+    }
+
+    /// Sends a list of objects to Output, ie:
+    /// "you, Adam, three apples and something".
+    /// Objects with the same short name are counted and
+    /// objects the viewer can't see are grouped at the end.
+    /// a() is used for each item.
+    /// The text is capitalized as needed.
+    pub fn a_list(self, objs: &[&dyn Object]) -> Self {
+        self.add_list(objs, false, false)
+    }
+
+    /// Sends a list of objects to Output, ie:
+    /// "you, Adam, the three apples and something".
+    /// Objects with the same short name are counted and
+    /// objects the viewer can't see are grouped at the end.
+    /// the() is used for each item.
+    /// The text is capitalized as needed.
+    pub fn the_list(self, objs: &[&dyn Object]) -> Self {
+        self.add_list(objs, true, false)
+    }
+
+    /// Are a and b named the same, so they can be counted together?
+    fn has_same_names(a: &dyn Object, b: &dyn Object) -> bool {
+        a.gender() == b.gender()
+            && a.short_name() == b.short_name()
+            && a.long_name() == b.long_name()
+            && a.short_plural_name() == b.short_plural_name()
+            && a.long_plural_name() == b.long_plural_name()
+            && a.is_short_proper() == b.is_short_proper()
+            && a.is_long_proper() == b.is_long_proper()
+    }
+
+    fn add_list(mut self, objs: &[&dyn Object], definite: bool, long: bool) -> Self {
+        let mut seen: Vec<(&dyn Object, usize)> = vec![];
+        let mut unseen: Vec<&dyn Object> = vec![];
+        for obj in objs {
+            if self.is_me(*obj) {
+                seen.push((*obj, 1));
+            } else if !self.o.can_see(*obj) {
+                unseen.push(*obj);
+            } else if let Some(entry) = seen.iter_mut().find(|(o, _)| {
+                Self::is_singular(obj.gender()) && Self::has_same_names(*o, *obj) && !self.is_me(*o)
+            }) {
+                entry.1 += 1;
+            } else {
+                seen.push((*obj, 1));
+            }
+        }
+        if self.narration == Narration::First {
+            // "Eva and I", not "I and Eva".
+            seen.sort_by_key(|(obj, _)| self.is_me(*obj));
+        }
+
+        let items = seen.len() + if unseen.is_empty() { 0 } else { 1 };
+        if items == 0 {
+            return self.s("nothing");
+        }
+        // The viewer has the case of the whole list, "Eva and me".
+        let subject = self.subject;
+        self.subject = Some(subject.unwrap_or_else(|| self.is_subject_position()));
+        for (i, (obj, count)) in seen.iter().enumerate() {
+            self = self.add_list_separator(i, items);
+            if *count > 1 {
+                let (name, is_proper, form) = if long {
+                    (
+                        obj.long_plural_name(),
+                        obj.is_long_plural_proper(),
+                        NameForm::LongPluralName,
+                    )
+                } else {
+                    (
+                        obj.short_plural_name(),
+                        obj.is_short_plural_proper(),
+                        NameForm::ShortPluralName,
+                    )
+                };
+                self = self.mention(*obj, form, |mut ob| {
+                    if definite && !is_proper {
+                        ob = ob.s("the");
+                    }
+                    ob.s(&number_word(*count)).s(name)
+                });
+            } else {
+                let (name, is_proper, form) = if long {
+                    (obj.long_name(), obj.is_long_proper(), NameForm::LongName)
+                } else {
+                    (obj.short_name(), obj.is_short_proper(), NameForm::ShortName)
+                };
+                self = if definite {
+                    self.add_the_word(*obj, name, is_proper, form)
+                } else {
+                    self.add_a_word(*obj, name, is_proper, form)
+                };
+            }
+        }
+        if !unseen.is_empty() {
+            self = self.add_list_separator(items - 1, items);
+            if unseen.len() == 1 {
+                let word = Self::unseen_word(unseen[0], unseen[0].is_short_proper());
+                self = self.mention(unseen[0], NameForm::Unseen, |ob| ob.s(word));
+            } else {
+                self = self.s("some things");
+            }
+        }
+        self.subject = subject;
+        self
+    }
+
+    fn add_list_separator(mut self, i: usize, items: usize) -> Self {
+        if i == 0 {
+            return self;
+        }
+        if i + 1 == items {
+            if self.oxford_comma && items > 2 {
+                self.add_space = false;
+                self = self.s(",");
+            }
+            self.s("and")
+        } else {
+            self.add_space = false;
+            self.s(",")
+        }
+    }
+
     /// The sentance will not have a dot added automatically.
     pub fn suppress_dot(mut self) -> Self {
         self.suppress_dot = true;
@@ -553,7 +1343,7 @@ impl<'a> OutputBuilder<'a> {
 }
 
 #[cfg(test)]
-#[allow(dead_code)]
+#[allow(dead_code, clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
 
@@ -592,6 +1382,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_number_word() {
+        assert_eq!(number_word(0), "zero");
+        assert_eq!(number_word(3), "three");
+        assert_eq!(number_word(19), "nineteen");
+        assert_eq!(number_word(20), "twenty");
+        assert_eq!(number_word(42), "forty-two");
+        assert_eq!(number_word(100), "100");
+    }
+
     #[test]
     fn test_last_char() {
         assert_eq!(last_char(""), None);
@@ -1,13 +1,190 @@
 use crate::suffix;
 use crate::{Gender, Named};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 /// Creates object implementing Named.
 pub struct Factory {
     pluralising_suffixes: suffix::Suffix<String>,
 }
 
-struct NamedImpl {
+/// The names of an object, as given to `Factory::create_from_def`.
+/// Only the short name is needed, the other names are
+/// created from it if they are missing.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NamedDef {
+    pub short_name: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub short_proper: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub long_name: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub long_proper: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub short_plural_name: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub short_plural_proper: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub long_plural_name: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub long_plural_proper: bool,
+    pub gender: Gender,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub thing: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub aliases: Vec<String>,
+    /// What to call the object when it can't be seen,
+    /// instead of "someone"/"something".
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unseen: Option<String>,
+}
+
+impl NamedDef {
+    /// Creates a NamedDef with just a short name.
+    pub fn new(short_name: &str, gender: Gender, thing: bool) -> Self {
+        Self {
+            short_name: String::from(short_name),
+            short_proper: false,
+            long_name: None,
+            long_proper: false,
+            short_plural_name: None,
+            short_plural_proper: false,
+            long_plural_name: None,
+            long_plural_proper: false,
+            gender,
+            thing,
+            aliases: vec![],
+            unseen: None,
+        }
+    }
+
+    /// Parses a name spec with inline metadata, ie:
+    /// "orc, old orc; male; creature; alias=greenskin".
+    ///
+    /// The names are given as for `Factory::create`. They can be followed
+    /// by metadata separated by ';':
+    /// * The gender; male, female, neuter, plural or uncountable.
+    ///   Neuter is used if it is not given.
+    /// * thing or creature. thing is used if it is not given.
+    /// * alias=name, can be given multiple times.
+    /// * unseen=name, what the object is called when it can't be seen.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut parts = spec.split(';');
+        let mut def = Self::new("", Gender::Neuter, true);
+        def.add_names(parts.next().unwrap_or_default())?;
+
+        let mut gender = None;
+        let mut thing = None;
+        for meta in parts.map(str::trim).filter(|m| !m.is_empty()) {
+            let (new_gender, new_thing) = match meta.to_lowercase().as_str() {
+                "male" => (Some(Gender::Male), None),
+                "female" => (Some(Gender::Female), None),
+                "neuter" => (Some(Gender::Neuter), None),
+                "plural" => (Some(Gender::Plural), None),
+                "uncountable" => (Some(Gender::Uncountable), None),
+                "thing" => (None, Some(true)),
+                "creature" => (None, Some(false)),
+                _ => {
+                    def.add_meta(meta)?;
+                    (None, None)
+                }
+            };
+            if new_gender.is_some() {
+                if gender.is_some() {
+                    return Err(format!("The gender is given twice in {:?}", spec));
+                }
+                gender = new_gender;
+            }
+            if new_thing.is_some() {
+                if thing.is_some() {
+                    return Err(format!("thing/creature is given twice in {:?}", spec));
+                }
+                thing = new_thing;
+            }
+        }
+        def.gender = gender.unwrap_or(Gender::Neuter);
+        def.thing = thing.unwrap_or(true);
+        Ok(def)
+    }
+
+    fn add_meta(&mut self, meta: &str) -> Result<(), String> {
+        let mut kv = meta.splitn(2, '=');
+        let key = kv.next().unwrap_or_default().trim();
+        let value = match kv.next().map(str::trim) {
+            Some(value) if !value.is_empty() => String::from(value),
+            Some(_) => return Err(format!("Missing value for {:?}", key)),
+            None => return Err(format!("Unknown metadata {:?}", meta)),
+        };
+        match key.to_lowercase().as_str() {
+            "alias" => self.aliases.push(value),
+            "unseen" => self.unseen = Some(value),
+            _ => return Err(format!("Unknown metadata {:?}", meta)),
+        }
+        Ok(())
+    }
+
+    /// Sets the names from a comma separated list, as for `Factory::create`.
+    fn add_names(&mut self, names: &str) -> Result<(), String> {
+        fn proper(name: &str) -> (String, bool) {
+            match name.strip_prefix('!') {
+                Some(name) => (String::from(name), true),
+                None => (String::from(name), false),
+            }
+        }
+
+        let mut error = None;
+        let mut list = vec![];
+        for s in names.split(',').map(|s| s.trim()) {
+            if let Some(alias) = s.strip_prefix('%') {
+                if alias.is_empty() {
+                    error = Some(format!("Empty alias in {:?}", names));
+                } else {
+                    self.aliases.push(String::from(alias));
+                }
+            } else {
+                if proper(s).0.is_empty() {
+                    error = Some(format!("Empty name in {:?}", names));
+                }
+                list.push(proper(s));
+            }
+        }
+        if list.len() > 4 {
+            error = Some(format!("More than four names in {:?}", names));
+        }
+        let mut list = list.into_iter();
+        if let Some((name, is_proper)) = list.next() {
+            self.short_name = name;
+            self.short_proper = is_proper;
+        } else {
+            error = Some(format!("No name in {:?}", names));
+        }
+        if let Some((name, is_proper)) = list.next() {
+            self.long_name = Some(name);
+            self.long_proper = is_proper;
+        }
+        if let Some((name, is_proper)) = list.next() {
+            self.short_plural_name = Some(name);
+            self.short_plural_proper = is_proper;
+        }
+        if let Some((name, is_proper)) = list.next() {
+            self.long_plural_name = Some(name);
+            self.long_plural_proper = is_proper;
+        }
+        match error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The Named created by Factory.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NamedImpl {
     names: Vec<String>,
+    aliases: Vec<String>,
+    unseen: Option<String>,
     short_proper_name: bool,
     long_proper_name: bool,
     short_proper_plural_name: bool,
@@ -71,70 +248,66 @@ impl Factory {
         let mut ret = String::from(name);
         if name.ends_with('s') {
             ret.push('e');
-        } else {
         }
         ret.push('s');
         ret
     }
 
-    pub fn create(&self, name: &str, gender: Gender, is_thing: bool) -> Box<dyn Named> {
-        let mut names: Vec<String> = name
-            .split(',')
-            .map(|s| String::from(s.trim_start()))
-            .collect();
-        if names.len() < 2 {
-            let long_name = names[0].clone();
-            names.push(long_name);
-        }
-        let short_proper_name = if let Some(c) = names[0].chars().next() {
-            c == '!'
-        } else {
-            false
-        };
-        if short_proper_name {
-            names[0].remove(0);
-        }
-        let long_proper_name = if let Some(c) = names[1].chars().next() {
-            c == '!'
-        } else {
-            false
+    /// Creates a Named from a comma separated list of names.
+    /// The names are short name, long name, short plural name and
+    /// long plural name. Names starting with '!' are proper names.
+    /// Names starting with '%' are aliases and can be anywhere in the list.
+    /// Empty names and more than four names are errors.
+    pub fn create(
+        &self,
+        name: &str,
+        gender: Gender,
+        is_thing: bool,
+    ) -> Result<Box<dyn Named>, String> {
+        let mut def = NamedDef::new("", gender, is_thing);
+        def.add_names(name)?;
+        Ok(Box::new(self.create_from_def(&def)) as Box<dyn Named>)
+    }
+
+    /// Creates a Named from a name spec with inline metadata,
+    /// see `NamedDef::parse` for the format.
+    pub fn create_from_spec(&self, spec: &str) -> Result<Box<dyn Named>, String> {
+        let def = NamedDef::parse(spec)?;
+        Ok(Box::new(self.create_from_def(&def)) as Box<dyn Named>)
+    }
+
+    /// Creates a NamedImpl from a NamedDef.
+    /// Missing names are created from the short name
+    /// and missing plural names are pluralized.
+    pub fn create_from_def(&self, def: &NamedDef) -> NamedImpl {
+        let (long_name, long_proper_name) = match &def.long_name {
+            Some(name) => (name.clone(), def.long_proper),
+            None => (def.short_name.clone(), def.short_proper),
         };
-        if names.len() < 3 {
-            let long_name = names[0].clone();
-            names.push(self.pluralize(&long_name));
-        }
-        if long_proper_name {
-            names[1].remove(0);
-        }
-        let short_proper_plural_name = if let Some(c) = names[2].chars().next() {
-            c == '!'
-        } else {
-            false
+        let (short_plural_name, short_proper_plural_name) = match &def.short_plural_name {
+            Some(name) => (name.clone(), def.short_plural_proper),
+            None => (self.pluralize(&def.short_name), false),
         };
-        if names.len() < 4 {
-            let long_name = names[1].clone();
-            names.push(self.pluralize(&long_name));
-        }
-        if short_proper_plural_name {
-            names[2].remove(0);
-        }
-        let long_proper_plural_name = if let Some(c) = names[3].chars().next() {
-            c == '!'
-        } else {
-            false
+        let (long_plural_name, long_proper_plural_name) = match &def.long_plural_name {
+            Some(name) => (name.clone(), def.long_plural_proper),
+            None => (self.pluralize(&long_name), false),
         };
-        if long_proper_plural_name {
-            names[3].remove(0);
-        }
-        Box::new(NamedImpl {
-            names,
-            short_proper_name,
+        NamedImpl {
+            names: vec![
+                def.short_name.clone(),
+                long_name,
+                short_plural_name,
+                long_plural_name,
+            ],
+            aliases: def.aliases.clone(),
+            unseen: def.unseen.clone(),
+            short_proper_name: def.short_proper,
             long_proper_name,
             short_proper_plural_name,
             long_proper_plural_name,
-            gender,
-            thing: is_thing,
-        }) as Box<dyn Named>
+            gender: def.gender,
+            thing: def.thing,
+        }
     }
 }
 
@@ -178,6 +351,58 @@ impl Named for NamedImpl {
     fn long_plural_name(&self) -> &str {
         self.names[3].as_str()
     }
+
+    fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+
+    fn unseen_name(&self) -> Option<&str> {
+        self.unseen.as_deref()
+    }
+}
+
+/// Words that don't refer to anything on their own.
+const ARTICLES: &[&str] = &["a", "an", "the"];
+
+/// Returns the lowercased words that can be used to refer to named.
+/// They are taken from all its names and aliases, but the articles.
+pub fn keywords<T: Named + ?Sized>(named: &T) -> Vec<String> {
+    let mut ret: Vec<String> = vec![];
+    let names = [
+        named.short_name(),
+        named.long_name(),
+        named.short_plural_name(),
+        named.long_plural_name(),
+    ];
+    for name in names
+        .iter()
+        .copied()
+        .chain(named.aliases().iter().map(String::as_str))
+    {
+        for word in name.split_whitespace() {
+            let word = word.to_lowercase();
+            if !ret.contains(&word) && !ARTICLES.contains(&word.as_str()) {
+                ret.push(word);
+            }
+        }
+    }
+    ret
+}
+
+/// Does text refer to named?
+/// It does if every word in text, but the articles,
+/// is one of named's keywords.
+pub fn is_named<T: Named + ?Sized>(named: &T, text: &str) -> bool {
+    let keywords = keywords(named);
+    let mut words = text
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .filter(|word| !ARTICLES.contains(&word.as_str()))
+        .peekable();
+    if words.peek().is_none() {
+        return false;
+    }
+    words.all(|word| keywords.contains(&word))
 }
 
 #[cfg(test)]
@@ -192,52 +417,221 @@ mod tests {
     #[test]
     fn short_name() {
         let nf = get_named_fac();
-        let ove = nf.create("!Ove, !Ove Svensson", Gender::Male, false);
+        let ove = nf
+            .create("!Ove, !Ove Svensson", Gender::Male, false)
+            .unwrap();
         assert_eq!(ove.short_name(), "Ove");
 
-        let eva = nf.create("Eva, Eva Stinasson", Gender::Female, false);
+        let eva = nf
+            .create("Eva, Eva Stinasson", Gender::Female, false)
+            .unwrap();
         assert_eq!(eva.short_name(), "Eva");
     }
 
     #[test]
     fn short_plural() {
         let nf = get_named_fac();
-        let man = nf.create("man, old man, mob, angry mob", Gender::Male, false);
+        let man = nf
+            .create("man, old man, mob, angry mob", Gender::Male, false)
+            .unwrap();
         assert_eq!(man.short_plural_name(), "mob");
 
-        let orc = nf.create("orc, blue orc", Gender::Male, false);
+        let orc = nf.create("orc, blue orc", Gender::Male, false).unwrap();
         assert_eq!(orc.short_plural_name(), "orcs");
 
-        let kiss = nf.create("kiss", Gender::Neuter, true);
+        let kiss = nf.create("kiss", Gender::Neuter, true).unwrap();
         assert_eq!(kiss.short_plural_name(), "kisses");
 
-        let knife = nf.create("knife, dull knife", Gender::Neuter, true);
+        let knife = nf
+            .create("knife, dull knife", Gender::Neuter, true)
+            .unwrap();
         assert_eq!(knife.short_plural_name(), "knives");
     }
 
     #[test]
     fn long_name() {
         let nf = get_named_fac();
-        let ove = nf.create("!Ove, !Ove Svensson", Gender::Male, false);
+        let ove = nf
+            .create("!Ove, !Ove Svensson", Gender::Male, false)
+            .unwrap();
         assert_eq!(ove.long_name(), "Ove Svensson");
 
-        let eva = nf.create("Eva, Eva Stinasson", Gender::Female, false);
+        let eva = nf
+            .create("Eva, Eva Stinasson", Gender::Female, false)
+            .unwrap();
         assert_eq!(eva.long_name(), "Eva Stinasson");
     }
 
+    #[test]
+    fn aliases() {
+        let nf = get_named_fac();
+        let gandalf = nf
+            .create(
+                "!Gandalf, %man, !Gandalf the gray, %gray",
+                Gender::Male,
+                false,
+            )
+            .unwrap();
+        assert_eq!(gandalf.short_name(), "Gandalf");
+        assert_eq!(gandalf.long_name(), "Gandalf the gray");
+        assert_eq!(gandalf.aliases(), &["man", "gray"]);
+
+        let orc = nf.create("orc, blue orc", Gender::Male, false).unwrap();
+        assert!(orc.aliases().is_empty());
+    }
+
+    #[test]
+    fn keywords() {
+        let nf = get_named_fac();
+        let gandalf = nf
+            .create("!Gandalf, !Gandalf the Gray, %old man", Gender::Male, false)
+            .unwrap();
+        assert_eq!(
+            super::keywords(gandalf.as_ref()),
+            vec!["gandalf", "gray", "gandalfs", "grays", "old", "man"]
+        );
+    }
+
+    #[test]
+    fn is_named() {
+        let nf = get_named_fac();
+        let gandalf = nf
+            .create(
+                "!Gandalf, !Gandalf the gray, %man, %gray",
+                Gender::Male,
+                false,
+            )
+            .unwrap();
+        assert!(super::is_named(gandalf.as_ref(), "gandalf"));
+        assert!(super::is_named(gandalf.as_ref(), "Gray man"));
+        assert!(super::is_named(gandalf.as_ref(), "gandalf the gray"));
+        assert!(!super::is_named(gandalf.as_ref(), "gray elf"));
+        assert!(!super::is_named(gandalf.as_ref(), ""));
+        assert!(!super::is_named(gandalf.as_ref(), "the"));
+
+        let knife = nf
+            .create("knife, dull knife", Gender::Neuter, true)
+            .unwrap();
+        assert!(super::is_named(knife.as_ref(), "dull knives"));
+        assert!(!super::is_named(knife.as_ref(), "sharp knife"));
+    }
+
+    #[test]
+    fn create_from_def() {
+        let nf = get_named_fac();
+        let mut def = NamedDef::new("Gandalf", Gender::Male, false);
+        def.short_proper = true;
+        def.long_name = Some(String::from("old man"));
+        def.aliases.push(String::from("wizard"));
+        let gandalf = nf.create_from_def(&def);
+        assert_eq!(gandalf.short_name(), "Gandalf");
+        assert!(gandalf.is_short_proper());
+        assert_eq!(gandalf.long_name(), "old man");
+        assert!(!gandalf.is_long_proper());
+        assert_eq!(gandalf.long_plural_name(), "old men");
+        assert_eq!(gandalf.aliases(), &["wizard"]);
+        assert_eq!(gandalf.unseen_name(), None);
+    }
+
+    #[test]
+    fn create_from_spec() {
+        let nf = get_named_fac();
+        let orc = nf
+            .create_from_spec("orc, old orc; male; creature; alias=greenskin")
+            .unwrap();
+        assert_eq!(orc.short_name(), "orc");
+        assert_eq!(orc.long_plural_name(), "old orcs");
+        assert_eq!(orc.gender(), Gender::Male);
+        assert!(!orc.is_thing());
+        assert_eq!(orc.aliases(), &["greenskin"]);
+
+        let knife = nf
+            .create_from_spec("knife, %blade; unseen=something sharp")
+            .unwrap();
+        assert_eq!(knife.long_name(), "knife");
+        assert_eq!(knife.gender(), Gender::Neuter);
+        assert!(knife.is_thing());
+        assert_eq!(knife.aliases(), &["blade"]);
+        assert_eq!(knife.unseen_name(), Some("something sharp"));
+    }
+
+    #[test]
+    fn create_from_bad_spec() {
+        let nf = get_named_fac();
+        for spec in &[
+            "",
+            "; male",
+            "%alias",
+            "orc,, orcs",
+            "a, b, c, d, e",
+            "orc; male; female",
+            "orc; thing; creature",
+            "orc; large",
+            "orc; alias=",
+            "orc; color=green",
+        ] {
+            assert!(nf.create_from_spec(spec).is_err(), "{:?}", spec);
+        }
+    }
+
+    #[test]
+    fn create_with_bad_names() {
+        let nf = get_named_fac();
+        for name in &["%alias", "", "apple, ", "a, b, c, d, e", "apple, %"] {
+            assert!(nf.create(name, Gender::Neuter, true).is_err(), "{:?}", name);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_def() {
+        let nf = get_named_fac();
+        let def: NamedDef = serde_json::from_str(
+            r#"{
+                "short_name": "knife",
+                "long_name": "dull knife",
+                "gender": "Neuter",
+                "thing": true,
+                "unseen": "something sharp"
+            }"#,
+        )
+        .unwrap();
+        let knife = nf.create_from_def(&def);
+        assert_eq!(knife.long_plural_name(), "dull knives");
+        assert_eq!(knife.gender(), Gender::Neuter);
+        assert!(knife.is_thing());
+        assert_eq!(knife.unseen_name(), Some("something sharp"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let nf = get_named_fac();
+        let def = NamedDef::new("woman", Gender::Female, false);
+        let woman = nf.create_from_def(&def);
+        let json = serde_json::to_string(&woman).unwrap();
+        let woman: NamedImpl = serde_json::from_str(&json).unwrap();
+        assert_eq!(woman.short_plural_name(), "women");
+        assert_eq!(woman.gender(), Gender::Female);
+    }
+
     #[test]
     fn long_plural() {
         let nf = get_named_fac();
-        let man = nf.create("man, old man, mob, angry mob", Gender::Male, false);
+        let man = nf
+            .create("man, old man, mob, angry mob", Gender::Male, false)
+            .unwrap();
         assert_eq!(man.long_plural_name(), "angry mob");
 
-        let orc = nf.create("orc, blue orc", Gender::Male, false);
+        let orc = nf.create("orc, blue orc", Gender::Male, false).unwrap();
         assert_eq!(orc.long_plural_name(), "blue orcs");
 
-        let kiss = nf.create("kiss", Gender::Neuter, true);
+        let kiss = nf.create("kiss", Gender::Neuter, true).unwrap();
         assert_eq!(kiss.long_plural_name(), "kisses");
 
-        let knife = nf.create("knife, dull knife", Gender::Neuter, true);
+        let knife = nf
+            .create("knife, dull knife", Gender::Neuter, true)
+            .unwrap();
         assert_eq!(knife.long_plural_name(), "dull knives");
     }
 }
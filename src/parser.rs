@@ -0,0 +1,476 @@
+//! Parses commands written by players, like "get 2.apple", "drop all knives"
+//! or "give the old elf's sword to gandalf", and resolves their noun phrases,
+//! like "the second red apple", against the Named objects that are in scope.
+
+use crate::named;
+use crate::Named;
+
+/// Which of the matching objects the phrase selects.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Selector {
+    /// No number was given, ie "apple" or "the knives".
+    Any,
+    /// The n:th matching object, counting from 1, ie "2.apple" or "second apple".
+    Nth(usize),
+    /// All matching objects, ie "all apples" or "all.apple".
+    All,
+}
+
+/// A parsed noun phrase.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NounPhrase {
+    /// The owner in a possessive phrase, ie "the old elf" in "the old elf's sword".
+    pub owner: Option<Box<NounPhrase>>,
+    pub selector: Selector,
+    /// The words naming the object(s), lowercased and without articles.
+    pub words: Vec<String>,
+}
+
+/// The result of resolving a NounPhrase.
+#[derive(Debug)]
+pub enum Resolved<'a, T: ?Sized> {
+    /// Nothing matched the phrase.
+    Nothing,
+    /// A single object matched.
+    One(&'a T),
+    /// The phrase was in plural or used "all".
+    Many(Vec<&'a T>),
+    /// The phrase could mean different objects.
+    Ambiguous(Vec<&'a T>),
+    /// The owner in a possessive phrase could be different objects,
+    /// they are given instead of the owned objects.
+    AmbiguousOwner(Vec<&'a T>),
+}
+
+/// A parsed command, "give the sword to gandalf".
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Command {
+    /// The first word, lowercased, "give".
+    pub verb: String,
+    /// The direct object, "the sword".
+    pub object: Option<NounPhrase>,
+    /// The preposition before the indirect object, "to".
+    pub preposition: Option<String>,
+    /// The indirect object, "gandalf".
+    pub indirect: Option<NounPhrase>,
+}
+
+/// The words that separate the direct and indirect objects of a command.
+const PREPOSITIONS: &[&str] = &[
+    "to", "from", "in", "into", "on", "onto", "at", "with", "under", "behind", "through",
+];
+
+const ORDINALS: [&str; 10] = [
+    "first", "second", "third", "fourth", "fifth", "sixth", "seventh", "eighth", "ninth", "tenth",
+];
+
+fn parse_ordinal(word: &str) -> Option<usize> {
+    if let Some(pos) = ORDINALS.iter().position(|o| *o == word) {
+        return Some(pos + 1);
+    }
+    for suffix in &["st", "nd", "rd", "th"] {
+        if let Some(num) = word.strip_suffix(suffix) {
+            if let Ok(n) = num.parse::<usize>() {
+                if n > 0 {
+                    return Some(n);
+                }
+            }
+        }
+    }
+    None
+}
+
+fn strip_possessive(word: &str) -> Option<&str> {
+    if let Some(w) = word.strip_suffix("'s") {
+        Some(w)
+    } else {
+        word.strip_suffix('\'')
+    }
+}
+
+/// Parses a noun phrase.
+pub fn parse(text: &str) -> Result<NounPhrase, String> {
+    let words: Vec<String> = text.split_whitespace().map(str::to_lowercase).collect();
+    parse_words(&words)
+}
+
+/// Parses a command; a verb, optionally followed by an object,
+/// and a preposition with an indirect object.
+/// "look at the apple" has no object, just an indirect object.
+pub fn parse_command(text: &str) -> Result<Command, String> {
+    let words: Vec<String> = text.split_whitespace().map(str::to_lowercase).collect();
+    let (verb, rest) = match words.split_first() {
+        Some((verb, rest)) => (verb.clone(), rest),
+        None => return Err(String::from("Expected a command")),
+    };
+    let (object, indirect) = match rest.iter().position(|w| PREPOSITIONS.contains(&w.as_str())) {
+        Some(pos) => (&rest[..pos], Some((&rest[pos], &rest[pos + 1..]))),
+        None => (rest, None),
+    };
+    let object = if object.is_empty() {
+        None
+    } else {
+        Some(parse_words(object)?)
+    };
+    let (preposition, indirect) = match indirect {
+        Some((prep, [])) => return Err(format!("Expected a noun phrase after {:?}", prep)),
+        Some((prep, words)) => (Some(prep.clone()), Some(parse_words(words)?)),
+        None => (None, None),
+    };
+    Ok(Command {
+        verb,
+        object,
+        preposition,
+        indirect,
+    })
+}
+
+fn parse_words(words: &[String]) -> Result<NounPhrase, String> {
+    if words.is_empty() {
+        return Err(String::from("Expected a noun phrase"));
+    }
+
+    // Possessives, "a's b's c" becomes owner "a's b" and "c".
+    let mut owner = None;
+    let mut words = words;
+    if let Some(pos) = words[..words.len() - 1]
+        .iter()
+        .rposition(|w| strip_possessive(w).is_some())
+    {
+        let mut owner_words = words[..=pos].to_vec();
+        let last = strip_possessive(&owner_words[pos]).unwrap_or_default();
+        owner_words[pos] = String::from(last);
+        owner = Some(Box::new(parse_words(&owner_words)?));
+        words = &words[pos + 1..];
+    }
+
+    let mut selector = Selector::Any;
+    let mut i = 0;
+    if words[0] == "all" || words[0] == "every" {
+        selector = Selector::All;
+        i += 1;
+        if words.get(i).map(String::as_str) == Some("of") {
+            i += 1;
+        }
+    }
+    if let Some(w) = words.get(i) {
+        if w == "the" || w == "a" || w == "an" || w == "some" {
+            i += 1;
+        }
+    }
+    if selector == Selector::Any {
+        if let Some(n) = words.get(i).and_then(|w| parse_ordinal(w)) {
+            selector = Selector::Nth(n);
+            i += 1;
+        }
+    }
+
+    let mut rest: Vec<String> = words[i..].to_vec();
+    if let Some(first) = rest.first_mut() {
+        if let Some(dot) = first.find('.') {
+            let (num, name) = first.split_at(dot);
+            let sel = if num == "all" {
+                Some(Selector::All)
+            } else {
+                num.parse::<usize>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .map(Selector::Nth)
+            };
+            if let (Some(sel), Selector::Any) = (sel, selector) {
+                selector = sel;
+                *first = String::from(&name[1..]);
+                if first.is_empty() {
+                    rest.remove(0);
+                }
+            }
+        }
+    }
+
+    if rest.is_empty() && selector != Selector::All {
+        return Err(String::from("Expected a name"));
+    }
+
+    Ok(NounPhrase {
+        owner,
+        selector,
+        words: rest,
+    })
+}
+
+fn last_word(s: &str) -> Option<String> {
+    s.split_whitespace().next_back().map(str::to_lowercase)
+}
+
+impl<'a, T: ?Sized> Resolved<'a, T> {
+    /// Did nothing match?
+    pub fn is_nothing(&self) -> bool {
+        matches!(self, Resolved::Nothing)
+    }
+}
+
+impl NounPhrase {
+    /// Does the phrase name obj?
+    pub fn is_match<T: Named + ?Sized>(&self, obj: &T) -> bool {
+        self.words.is_empty() || named::is_named(obj, &self.words.join(" "))
+    }
+
+    /// Is the phrase in plural form for obj, ie "knives" for a knife?
+    pub fn is_plural_for<T: Named + ?Sized>(&self, obj: &T) -> bool {
+        let word = match self.words.last() {
+            Some(word) => word,
+            None => return false,
+        };
+        let singular = last_word(obj.short_name());
+        let plural = last_word(obj.short_plural_name());
+        plural.as_ref() == Some(word) && plural != singular
+    }
+
+    /// Resolves the phrase against candidates.
+    /// Possessive phrases never match.
+    pub fn resolve<'a, T: Named + ?Sized>(&self, candidates: &[&'a T]) -> Resolved<'a, T> {
+        self.resolve_owned(candidates, |_, _| false)
+    }
+
+    /// Resolves the phrase against candidates.
+    /// owns(owner, obj) should tell if owner has obj, it is used
+    /// for possessive phrases. The owner must also be among the candidates.
+    pub fn resolve_owned<'a, T, F>(&self, candidates: &[&'a T], owns: F) -> Resolved<'a, T>
+    where
+        T: Named + ?Sized,
+        F: Fn(&T, &T) -> bool,
+    {
+        self.resolve_dyn(candidates, &owns)
+    }
+
+    fn resolve_dyn<'a, T>(
+        &self,
+        candidates: &[&'a T],
+        owns: &dyn Fn(&T, &T) -> bool,
+    ) -> Resolved<'a, T>
+    where
+        T: Named + ?Sized,
+    {
+        let owner = match &self.owner {
+            None => None,
+            Some(owner) => match owner.resolve_dyn(candidates, owns) {
+                Resolved::One(owner) => Some(owner),
+                Resolved::Nothing => return Resolved::Nothing,
+                Resolved::Many(owners)
+                | Resolved::Ambiguous(owners)
+                | Resolved::AmbiguousOwner(owners) => return Resolved::AmbiguousOwner(owners),
+            },
+        };
+        let matches: Vec<&'a T> = candidates
+            .iter()
+            .copied()
+            .filter(|c| owner.map_or(true, |owner| owns(owner, c)))
+            .filter(|c| self.is_match(*c))
+            .collect();
+        if matches.is_empty() {
+            return Resolved::Nothing;
+        }
+        match self.selector {
+            Selector::All => Resolved::Many(matches),
+            Selector::Nth(n) => match matches.get(n - 1) {
+                Some(obj) => Resolved::One(obj),
+                None => Resolved::Nothing,
+            },
+            Selector::Any => {
+                if self.is_plural_for(matches[0]) {
+                    Resolved::Many(matches)
+                } else if matches
+                    .iter()
+                    .all(|m| m.long_name() == matches[0].long_name())
+                {
+                    Resolved::One(matches[0])
+                } else {
+                    Resolved::Ambiguous(matches)
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::named::Factory;
+    use crate::Gender;
+
+    fn long_names<'a>(r: Resolved<'a, dyn Named>) -> (&'static str, Vec<&'a str>) {
+        match r {
+            Resolved::Nothing => ("nothing", vec![]),
+            Resolved::One(o) => ("one", vec![o.long_name()]),
+            Resolved::Many(v) => ("many", v.into_iter().map(|o| o.long_name()).collect()),
+            Resolved::Ambiguous(v) => ("ambiguous", v.into_iter().map(|o| o.long_name()).collect()),
+            Resolved::AmbiguousOwner(v) => (
+                "ambiguous owner",
+                v.into_iter().map(|o| o.long_name()).collect(),
+            ),
+        }
+    }
+
+    fn parsed(owner: Option<NounPhrase>, selector: Selector, words: &[&str]) -> NounPhrase {
+        NounPhrase {
+            owner: owner.map(Box::new),
+            selector,
+            words: words.iter().map(|w| String::from(*w)).collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("apple"), Ok(parsed(None, Selector::Any, &["apple"])));
+        assert_eq!(
+            parse("2.apple"),
+            Ok(parsed(None, Selector::Nth(2), &["apple"]))
+        );
+        assert_eq!(
+            parse("The second red Apple"),
+            Ok(parsed(None, Selector::Nth(2), &["red", "apple"]))
+        );
+        assert_eq!(
+            parse("3rd knife"),
+            Ok(parsed(None, Selector::Nth(3), &["knife"]))
+        );
+        assert_eq!(
+            parse("all knives"),
+            Ok(parsed(None, Selector::All, &["knives"]))
+        );
+        assert_eq!(
+            parse("all of the knives"),
+            Ok(parsed(None, Selector::All, &["knives"]))
+        );
+        assert_eq!(
+            parse("all.apple"),
+            Ok(parsed(None, Selector::All, &["apple"]))
+        );
+        assert_eq!(parse("all"), Ok(parsed(None, Selector::All, &[])));
+        assert_eq!(
+            parse("the old elf's sword"),
+            Ok(parsed(
+                Some(parsed(None, Selector::Any, &["old", "elf"])),
+                Selector::Any,
+                &["sword"]
+            ))
+        );
+        assert!(parse("").is_err());
+        assert!(parse("the").is_err());
+    }
+
+    #[test]
+    fn test_parse_command() {
+        let command = |verb: &str, object, prep: Option<&str>, indirect| Command {
+            verb: String::from(verb),
+            object,
+            preposition: prep.map(String::from),
+            indirect,
+        };
+        assert_eq!(
+            parse_command("get 2.apple"),
+            Ok(command(
+                "get",
+                Some(parsed(None, Selector::Nth(2), &["apple"])),
+                None,
+                None
+            ))
+        );
+        assert_eq!(
+            parse_command("Drop all knives"),
+            Ok(command(
+                "drop",
+                Some(parsed(None, Selector::All, &["knives"])),
+                None,
+                None
+            ))
+        );
+        assert_eq!(
+            parse_command("give the old elf's sword to gandalf"),
+            Ok(command(
+                "give",
+                Some(parsed(
+                    Some(parsed(None, Selector::Any, &["old", "elf"])),
+                    Selector::Any,
+                    &["sword"]
+                )),
+                Some("to"),
+                Some(parsed(None, Selector::Any, &["gandalf"]))
+            ))
+        );
+        assert_eq!(
+            parse_command("look at the second red apple"),
+            Ok(command(
+                "look",
+                None,
+                Some("at"),
+                Some(parsed(None, Selector::Nth(2), &["red", "apple"]))
+            ))
+        );
+        assert_eq!(parse_command("look"), Ok(command("look", None, None, None)));
+        assert!(parse_command("").is_err());
+        assert!(parse_command("put apple in").is_err());
+        assert!(parse_command("get the").is_err());
+    }
+
+    #[test]
+    fn test_resolve() {
+        let nf = Factory::from_reader(&mut std::io::Cursor::new("fe:ves\n")).unwrap();
+        let red = nf.create("apple, red apple", Gender::Neuter, true).unwrap();
+        let green = nf
+            .create("apple, green apple", Gender::Neuter, true)
+            .unwrap();
+        let knife = nf
+            .create("knife, dull knife", Gender::Neuter, true)
+            .unwrap();
+        let knife2 = nf
+            .create("knife, dull knife", Gender::Neuter, true)
+            .unwrap();
+        let objs: Vec<&dyn Named> = vec![&*red, &*green, &*knife, &*knife2];
+
+        let resolve = |text: &str| long_names(parse(text).unwrap().resolve(&objs));
+
+        assert_eq!(resolve("green apple"), ("one", vec!["green apple"]));
+        assert_eq!(resolve("second apple"), ("one", vec!["green apple"]));
+        assert_eq!(
+            resolve("apple"),
+            ("ambiguous", vec!["red apple", "green apple"])
+        );
+        assert_eq!(resolve("knife"), ("one", vec!["dull knife"]));
+        assert_eq!(
+            resolve("knives"),
+            ("many", vec!["dull knife", "dull knife"])
+        );
+        assert_eq!(resolve("all").1.len(), 4);
+        assert_eq!(resolve("3.apple"), ("nothing", vec![]));
+        assert_eq!(resolve("sword"), ("nothing", vec![]));
+    }
+
+    #[test]
+    fn test_resolve_owned() {
+        let nf = Factory::from_reader(&mut std::io::Cursor::new("")).unwrap();
+        let elf = nf.create("elf, old elf", Gender::Male, false).unwrap();
+        let orc = nf.create("orc, old orc", Gender::Male, false).unwrap();
+        let sword = nf
+            .create("sword, long sword", Gender::Neuter, true)
+            .unwrap();
+        let sword2 = nf
+            .create("sword, rusty sword", Gender::Neuter, true)
+            .unwrap();
+        let objs: Vec<&dyn Named> = vec![&*elf, &*orc, &*sword, &*sword2];
+        fn owns<T: Named + ?Sized>(owner: &T, obj: &T) -> bool {
+            (owner.short_name() == "elf" && obj.long_name() == "long sword")
+                || (owner.short_name() == "orc" && obj.long_name() == "rusty sword")
+        }
+
+        let resolve = |text: &str| long_names(parse(text).unwrap().resolve_owned(&objs, owns));
+
+        assert_eq!(resolve("the old elf's sword"), ("one", vec!["long sword"]));
+        assert_eq!(resolve("orc's sword"), ("one", vec!["rusty sword"]));
+        assert_eq!(
+            resolve("old's sword"),
+            ("ambiguous owner", vec!["old elf", "old orc"])
+        );
+        assert!(parse("elf's sword").unwrap().resolve(&objs).is_nothing());
+    }
+}
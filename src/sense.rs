@@ -0,0 +1,121 @@
+//! Templates that fall back on other senses, for blind, deaf
+//! and dark-room play.
+
+use crate::templates::{lookup, Context, ObjectRef, Template, TemplateText};
+use crate::{Object, Output};
+
+/// A sense a Viewer can notice an Object with.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Sense {
+    See,
+    Hear,
+    Smell,
+    Feel,
+}
+
+impl Sense {
+    /// The verb given to `Viewer::can`, "see", "hear", "smell" or "feel".
+    pub fn verb(self) -> &'static str {
+        match self {
+            Sense::See => "see",
+            Sense::Hear => "hear",
+            Sense::Smell => "smell",
+            Sense::Feel => "feel",
+        }
+    }
+
+    pub fn from_verb(verb: &str) -> Option<Self> {
+        match verb {
+            "see" => Some(Sense::See),
+            "hear" => Some(Sense::Hear),
+            "smell" => Some(Sense::Smell),
+            "feel" => Some(Sense::Feel),
+            _ => None,
+        }
+    }
+
+    /// Can out's viewer notice who with the sense?
+    /// Viewers always notice themselves.
+    pub fn can(self, out: &dyn Output, who: &dyn Object) -> bool {
+        if out.is_me(who) {
+            return true;
+        }
+        match self {
+            Sense::See => out.can_see(who),
+            _ => out.can(self.verb(), who),
+        }
+    }
+}
+
+/// A Template that renders the first of its templates whose sense
+/// the viewer can notice the Object with, like:
+/// * see: "\The(me) give\s(me) \a(obj) to \the(env)."
+/// * hear: "You hear someone moving nearby."
+/// * feel: "You feel a draft."
+///
+/// Nothing is rendered if the viewer can't notice the Object at all.
+/// If who doesn't refer to an Object, but to a number or a string,
+/// there is no Object to notice and the first template is rendered.
+pub struct SenseChain {
+    who: String,
+    chain: Vec<(Sense, Box<dyn Template>)>,
+}
+
+impl SenseChain {
+    /// Creates an empty chain for the Object called who in the Context,
+    /// "me", "obj", "env" or any name given to `Context::get`.
+    pub fn new(who: &str) -> Self {
+        Self {
+            who: String::from(who),
+            chain: vec![],
+        }
+    }
+
+    /// Adds template to the end of the chain.
+    pub fn or<T>(mut self, sense: Sense, template: T) -> Self
+    where
+        T: Template + 'static,
+    {
+        self.chain.push((sense, Box::new(template)));
+        self
+    }
+
+    /// Parses a chain from lines like "hear: You hear someone moving nearby.",
+    /// in the order they are tried.
+    pub fn parse(who: &str, text: &str) -> Result<Self, String> {
+        let mut chain = Self::new(who);
+        for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            let (verb, template) = match line.split_once(':') {
+                Some((verb, template)) => (verb.trim(), template.trim()),
+                None => return Err(format!("Missing sense in {:?}", line)),
+            };
+            let sense = match Sense::from_verb(verb) {
+                Some(sense) => sense,
+                None => return Err(format!("Unknown sense {:?}", verb)),
+            };
+            chain = chain.or(sense, TemplateText::parse(template)?);
+        }
+        Ok(chain)
+    }
+
+    /// The template to render for out's viewer, if any.
+    fn pick(&self, ctx: &dyn Context, out: &dyn Output) -> Option<&dyn Template> {
+        let who = match lookup(ctx, &self.who) {
+            ObjectRef::Object(obj) | ObjectRef::CountedObject(_, obj) => obj,
+            // There is nothing to notice, the first template is used.
+            _ => return self.chain.first().map(|(_, t)| t.as_ref()),
+        };
+        self.chain
+            .iter()
+            .find(|(sense, _)| sense.can(out, who))
+            .map(|(_, t)| t.as_ref())
+    }
+}
+
+impl Template for SenseChain {
+    fn render(&self, ctx: &dyn Context, out: &mut dyn Output) {
+        if let Some(template) = self.pick(ctx, out) {
+            template.render(ctx, out);
+        }
+    }
+}
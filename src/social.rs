@@ -0,0 +1,247 @@
+//! Socials, emote commands like "smile" and "hug Eva".
+
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::broadcast::{act_with, Act, Role};
+use crate::templates::{Context, ObjectRef, Template, TemplateText};
+use crate::{Object, Output};
+
+/// The template texts of a social, as given to `Socials::add`.
+/// In the templates, me is the one doing the social and obj the target.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SocialDef {
+    pub name: String,
+    /// Used without a target, "\The(me) smile\s(me)."
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub none: Option<String>,
+    /// Used with a target, "\The(me) smile\s(me) at \the(obj)."
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub target: Option<String>,
+    /// Used when the target is me, instead of target.
+    #[cfg_attr(feature = "serde", serde(default, rename = "self"))]
+    pub self_target: Option<String>,
+    /// Used, instead of target, for the viewers that can't see the target.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unseen: Option<String>,
+}
+
+/// A parsed SocialDef.
+pub struct Social {
+    name: String,
+    none: Option<TemplateText>,
+    target: Option<TemplateText>,
+    self_target: Option<TemplateText>,
+    unseen: Option<TemplateText>,
+}
+
+fn parse(name: &str, text: &Option<String>) -> Result<Option<TemplateText>, String> {
+    text.as_deref()
+        .map(TemplateText::parse)
+        .transpose()
+        .map_err(|e| format!("The social {:?}: {}", name, e))
+}
+
+impl Social {
+    pub fn from_def(def: &SocialDef) -> Result<Self, String> {
+        let social = Self {
+            name: def.name.clone(),
+            none: parse(&def.name, &def.none)?,
+            target: parse(&def.name, &def.target)?,
+            self_target: parse(&def.name, &def.self_target)?,
+            unseen: parse(&def.name, &def.unseen)?,
+        };
+        if social.none.is_none() && social.target.is_none() {
+            return Err(format!("The social {:?} has no templates", def.name));
+        }
+        Ok(social)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Can the social be used without a target?
+    pub fn has_none(&self) -> bool {
+        self.none.is_some()
+    }
+
+    /// Can the social be used with a target?
+    pub fn has_target(&self) -> bool {
+        self.target.is_some()
+    }
+
+    /// Sends the social, done by actor to target, to each of the outputs.
+    /// Each viewer gets the text from their perspective, "You hug Eva.",
+    /// "Adam hugs you." or "Adam hugs Eva.".
+    ///
+    /// Returns an error, and sends nothing, if the social can't be
+    /// used with, or without, a target.
+    pub fn act<'o, I>(
+        &self,
+        actor: &dyn Object,
+        target: Option<&dyn Object>,
+        outputs: I,
+    ) -> Result<(), String>
+    where
+        I: IntoIterator<Item = &'o mut dyn Output>,
+    {
+        let is_reflexive = target.is_some_and(|target| target.is_same(actor));
+        let template: &dyn Template = match (target, &self.none, &self.target, &self.self_target) {
+            (None, Some(none), _, _) => none,
+            (Some(_), _, Some(target), _) => target,
+            (Some(_), _, None, Some(self_target)) if is_reflexive => self_target,
+            (None, ..) => return Err(format!("{} needs a target", self.name)),
+            (Some(_), ..) => return Err(format!("{} can't have a target", self.name)),
+        };
+        let mut act = Act::new(template, template, template);
+        if let Some(self_target) = &self.self_target {
+            act = act.reflexive(self_target);
+        }
+        let ctx = SocialContext { actor, target };
+        act_with(&ctx, actor, target, outputs, |role, out| {
+            match (&self.unseen, target) {
+                // Others that can't see the target get the unseen template.
+                (Some(unseen), Some(target))
+                    if role == Role::Other && !is_reflexive && !out.can_see(target) =>
+                {
+                    Some(unseen)
+                }
+                _ => act.get(role, is_reflexive),
+            }
+        });
+        Ok(())
+    }
+}
+
+/// The Context the templates of a Social are rendered with.
+struct SocialContext<'a> {
+    actor: &'a dyn Object,
+    target: Option<&'a dyn Object>,
+}
+
+impl<'a> Context for SocialContext<'a> {
+    fn get_me(&self) -> ObjectRef<'_> {
+        ObjectRef::Object(self.actor)
+    }
+
+    fn get_obj(&self) -> ObjectRef<'_> {
+        match self.target {
+            Some(target) => ObjectRef::Object(target),
+            None => ObjectRef::String(""),
+        }
+    }
+
+    fn get_env(&self) -> ObjectRef<'_> {
+        ObjectRef::String("")
+    }
+
+    fn get(&self, _who: &str) -> ObjectRef<'_> {
+        ObjectRef::String("")
+    }
+}
+
+/// Socials by name.
+#[derive(Default)]
+pub struct Socials {
+    socials: HashMap<String, Social>,
+}
+
+impl Socials {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The socials in data/socials.txt; smile, bow, wave and hug.
+    pub fn standard() -> Self {
+        let mut data = include_str!("../data/socials.txt").as_bytes();
+        Self::from_reader(&mut data).expect("the standard socials are valid")
+    }
+
+    /// Reads socials from a text like:
+    /// ```text
+    /// # A comment.
+    /// [smile]
+    /// none: \The(me) smile\s(me) happily.
+    /// target: \The(me) smile\s(me) at \the(obj).
+    /// self: \The(me) smile\s(me) at \himself(me).
+    /// unseen: \The(me) smile\s(me) at someone you can't see.
+    /// ```
+    /// Each social starts with its name in brackets,
+    /// followed by its templates, see SocialDef.
+    pub fn from_reader(buff: &mut dyn std::io::BufRead) -> Result<Self, String> {
+        let mut socials = Self::new();
+        let mut def: Option<SocialDef> = None;
+        let mut nr = 0;
+        let mut line = String::new();
+        loop {
+            let len = buff
+                .read_line(&mut line)
+                .map_err(|e| format!("Could not read line {}: {}", nr + 1, e))?;
+            if len == 0 {
+                break;
+            }
+            nr += 1;
+            let text = line.trim();
+            if text.is_empty() || text.starts_with('#') {
+                // Skip it.
+            } else if text.starts_with('[') && text.ends_with(']') {
+                if let Some(def) = def.take() {
+                    socials.add(&def)?;
+                }
+                def = Some(SocialDef {
+                    name: String::from(text[1..text.len() - 1].trim()),
+                    ..SocialDef::default()
+                });
+            } else {
+                let current = match def.as_mut() {
+                    Some(def) => def,
+                    None => return Err(format!("No [social] before line {}", nr)),
+                };
+                let (key, template) = match text.split_once(':') {
+                    Some((key, template)) => (key.trim(), Some(String::from(template.trim()))),
+                    None => {
+                        return Err(format!(
+                            "The social has the wrong format on line {}, should be \
+                             variant: template: {:?}",
+                            nr, text
+                        ))
+                    }
+                };
+                match key {
+                    "none" => current.none = template,
+                    "target" => current.target = template,
+                    "self" => current.self_target = template,
+                    "unseen" => current.unseen = template,
+                    _ => return Err(format!("Unknown variant {:?} on line {}", key, nr)),
+                }
+            }
+            line.clear();
+        }
+        if let Some(def) = def {
+            socials.add(&def)?;
+        }
+        Ok(socials)
+    }
+
+    /// Adds or replaces a social.
+    pub fn add(&mut self, def: &SocialDef) -> Result<(), String> {
+        let social = Social::from_def(def)?;
+        self.socials.insert(def.name.clone(), social);
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Social> {
+        self.socials.get(name)
+    }
+
+    /// The names of the socials, sorted.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.socials.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+}
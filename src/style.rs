@@ -0,0 +1,86 @@
+//! Style names and the registry they are checked against.
+
+use std::collections::HashSet;
+
+/// The styles in `Registry::standard()`.
+pub const STANDARD: &[&str] = &[
+    "bold",
+    "dim",
+    "italic",
+    "underline",
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "magenta",
+    "cyan",
+    "white",
+    "item",
+    "npc",
+    "player",
+    "speech",
+    "exit",
+    "room",
+];
+
+/// The style names that can be used in templates.
+#[derive(Clone, Debug, Default)]
+pub struct Registry {
+    names: HashSet<String>,
+}
+
+impl Registry {
+    /// A Registry without any styles.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A Registry with the STANDARD styles.
+    pub fn standard() -> Self {
+        let mut registry = Self::new();
+        for name in STANDARD {
+            registry.add(name);
+        }
+        registry
+    }
+
+    /// Adds the style name.
+    pub fn with(mut self, name: &str) -> Self {
+        self.add(name);
+        self
+    }
+
+    /// Adds the style name.
+    pub fn add(&mut self, name: &str) {
+        self.names.insert(String::from(name));
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.names.contains(name)
+    }
+
+    /// Returns an error if name is not registered.
+    pub fn check(&self, name: &str) -> Result<(), String> {
+        if self.contains(name) {
+            Ok(())
+        } else {
+            Err(format!("Unknown style {:?}", name))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry() {
+        let registry = Registry::standard().with("spell");
+        assert!(registry.contains("bold"));
+        assert!(registry.contains("spell"));
+        assert!(registry.check("npc").is_ok());
+        assert!(registry.check("blink").is_err());
+        assert!(!Registry::new().contains("bold"));
+    }
+}
@@ -1,10 +1,15 @@
-use crate::{Object, Output};
+use std::convert::TryFrom;
+
+use crate::style::Registry;
+use crate::{Object, ObjectGroup, Output, OutputBuilder};
 
 /**
  * Objects are used in templates for obj, env etc.
  */
 pub enum ObjectRef<'a> {
     Object(&'a dyn Object),
+    /// A number of the object, the count must be positive.
+    /// A TemplateText that refers to a non-positive count renders nothing.
     CountedObject(i64, &'a dyn Object),
     Int(i64),
     String(&'a str),
@@ -25,15 +30,399 @@ pub trait Context {
  * Template contains the text's to generate a text.
  */
 pub trait Template {
-    fn render(&self, ctx: &dyn Context, out: &dyn Output);
+    fn render(&self, ctx: &dyn Context, out: &mut dyn Output);
+}
+
+/// The codes that can be used in template texts and their number of arguments.
+const CODES: &[(&str, usize)] = &[
+    ("the", 1),
+    ("the_", 1),
+    ("a", 1),
+    ("a_", 1),
+    ("thes", 1),
+    ("thes_", 1),
+    ("my", 2),
+    ("my_", 2),
+    ("he", 1),
+    ("he_s", 1),
+    ("he_has", 1),
+    ("his", 1),
+    ("hiss", 1),
+    ("him", 1),
+    ("himself", 1),
+    ("is", 1),
+    ("has", 1),
+    ("s", 1),
+    ("ing", 1),
+    ("ed", 1),
+    ("v", 2),
+    ("v_not", 2),
+    ("ask", 2),
+    ("ask_not", 2),
+    ("num", 1),
+    ("snum", 1),
+    ("str", 1),
+    // \style() ends the latest style.
+    ("style", 1),
+];
+
+#[derive(Debug)]
+enum Part {
+    Text(String),
+    Code {
+        name: String,
+        capitalize: bool,
+        args: Vec<String>,
+    },
+}
+
+/// A template text, like "\The(me) give\s(me) \a(obj) to \the_(env)."
+///
+/// The codes refer to objects in a Context by name; "me", "obj", "env"
+/// or any other name given to `Context::get`.
+/// The leading case of the code determines the case of the word.
+#[derive(Debug)]
+pub struct TemplateText {
+    parts: Vec<Part>,
+}
+
+impl TemplateText {
+    /// Parses a template text.
+    /// The styles are checked against `style::Registry::standard()`.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        Self::parse_with(text, &Registry::standard())
+    }
+
+    /// Parses a template text, with the styles
+    /// checked against registry.
+    pub fn parse_with(text: &str, registry: &Registry) -> Result<Self, String> {
+        let mut parts = vec![];
+        let mut literal = String::new();
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                literal.push(c);
+                continue;
+            }
+            if chars.peek() == Some(&'\\') {
+                chars.next();
+                literal.push('\\');
+                continue;
+            }
+            let mut name = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_alphanumeric() || c == '_' {
+                    name.push(c);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            if chars.next() != Some('(') {
+                return Err(format!("Expected '(' after \\{} in {:?}", name, text));
+            }
+            let mut args = String::new();
+            loop {
+                match chars.next() {
+                    Some(')') => break,
+                    Some(c) => args.push(c),
+                    None => return Err(format!("Missing ')' after \\{} in {:?}", name, text)),
+                }
+            }
+            let args: Vec<String> = if args.trim().is_empty() {
+                vec![]
+            } else {
+                args.split(',').map(|a| String::from(a.trim())).collect()
+            };
+            let capitalize = name.chars().next().is_some_and(char::is_uppercase);
+            let name = name.to_lowercase();
+            match CODES.iter().find(|code| code.0 == name) {
+                None => return Err(format!("Unknown code \\{} in {:?}", name, text)),
+                Some(_) if name == "style" && args.len() <= 1 => {
+                    if let Some(style) = args.first() {
+                        registry
+                            .check(style)
+                            .map_err(|e| format!("{} in {:?}", e, text))?;
+                    }
+                }
+                Some((_, n)) if *n != args.len() => {
+                    return Err(format!(
+                        "\\{} should have {} argument(s) in {:?}",
+                        name, n, text
+                    ))
+                }
+                _ => (),
+            }
+            if !literal.is_empty() {
+                parts.push(Part::Text(literal));
+                literal = String::new();
+            }
+            parts.push(Part::Code {
+                name,
+                capitalize,
+                args,
+            });
+        }
+        if !literal.is_empty() {
+            parts.push(Part::Text(literal));
+        }
+        Ok(Self { parts })
+    }
+
+    /// Renders the text with the objects in ctx to the OutputBuilder.
+    pub fn build<'a>(&self, ctx: &dyn Context, mut ob: OutputBuilder<'a>) -> OutputBuilder<'a> {
+        // Should the next word be written without a space before it?
+        let mut attach = false;
+        for (i, part) in self.parts.iter().enumerate() {
+            match part {
+                Part::Text(text) => {
+                    let who = match self.verb_ending_after(i) {
+                        Some(who) if !text.ends_with(char::is_whitespace) => Some(who),
+                        _ => None,
+                    };
+                    let words: Vec<&str> = text.split_whitespace().collect();
+                    for (j, word) in words.iter().enumerate() {
+                        if j == 0 {
+                            ob.add_space =
+                                ob.has_text() && (!attach || text.starts_with(char::is_whitespace));
+                        }
+                        ob = match who {
+                            Some(who) if j + 1 == words.len() => {
+                                Self::build_verb(ctx, ob, word, who)
+                            }
+                            _ => ob.s(word),
+                        };
+                    }
+                    attach = !text.ends_with(char::is_whitespace);
+                }
+                Part::Code { name, .. } if name == "s" => {
+                    // The verb was already written by the text before it.
+                    attach = true;
+                }
+                Part::Code { name, args, .. } if name == "style" => {
+                    // Styles don't change the spacing.
+                    ob = match args.first() {
+                        Some(style) => ob.style(style),
+                        None => ob.end_style(),
+                    };
+                }
+                Part::Code {
+                    name,
+                    capitalize,
+                    args,
+                } => {
+                    ob.add_space = ob.has_text() && !attach;
+                    ob.cap_it = *capitalize;
+                    ob = Self::build_code(ctx, ob, name, args);
+                    attach = true;
+                }
+            }
+        }
+        ob
+    }
+
+    /// The argument of the \\s code that the word at the end of
+    /// the text part i is the verb of, like "give\\s(me)".
+    /// Styles between them are skipped.
+    fn verb_ending_after(&self, i: usize) -> Option<&str> {
+        let next = self.parts[i + 1..].iter().find(|part| match part {
+            Part::Code { name, .. } => name != "style",
+            Part::Text(_) => true,
+        });
+        match next {
+            Some(Part::Code { name, args, .. }) if name == "s" => Some(&args[0]),
+            _ => None,
+        }
+    }
+
+    /// Writes verb conjugated to agree with the object that who refers to.
+    fn build_verb<'a>(
+        ctx: &dyn Context,
+        ob: OutputBuilder<'a>,
+        verb: &str,
+        who: &str,
+    ) -> OutputBuilder<'a> {
+        match lookup(ctx, who) {
+            ObjectRef::Int(_) | ObjectRef::String(_) => ob.s(verb),
+            r => with_ref(ob, r, |ob, obj| ob.v(&obj, verb)),
+        }
+    }
+
+    /// Does the text refer to a counted object with a non-positive count?
+    /// Only the arguments that are objects are looked up, not verbs or styles.
+    fn has_bad_count(&self, ctx: &dyn Context) -> bool {
+        self.parts.iter().any(|part| {
+            let objects = match part {
+                Part::Code { name, .. } if matches!(name.as_str(), "ing" | "ed" | "style") => {
+                    &[][..]
+                }
+                Part::Code { name, args, .. } if name == "my" || name == "my_" => &args[..2],
+                Part::Code { args, .. } => &args[..1],
+                Part::Text(_) => &[][..],
+            };
+            objects
+                .iter()
+                .any(|arg| matches!(lookup(ctx, arg), ObjectRef::CountedObject(n, _) if n <= 0))
+        })
+    }
+
+    fn build_code<'a>(
+        ctx: &dyn Context,
+        ob: OutputBuilder<'a>,
+        name: &str,
+        args: &[String],
+    ) -> OutputBuilder<'a> {
+        // These codes take a verb, not an object.
+        match name {
+            "ing" => return ob.ing(&args[0]),
+            "ed" => return ob.ed(&args[0]),
+            _ => (),
+        }
+        let who = lookup(ctx, &args[0]);
+        match name {
+            "num" => match who {
+                ObjectRef::Int(n) | ObjectRef::CountedObject(n, _) => ob.s(&n.to_string()),
+                other => with_ref(ob, other, |ob, obj| ob.s(obj.short_name())),
+            },
+            "snum" => match who {
+                ObjectRef::Int(n) | ObjectRef::CountedObject(n, _) => match usize::try_from(n) {
+                    Ok(n) => ob.s(&crate::number_word(n)),
+                    Err(_) => ob.s(&n.to_string()),
+                },
+                other => with_ref(ob, other, |ob, obj| ob.s(obj.short_name())),
+            },
+            "str" => with_ref(ob, who, |ob, obj| ob.s(obj.short_name())),
+            "my" | "my_" => {
+                let what = lookup(ctx, &args[1]);
+                with_ref(ob, who, |ob, who| {
+                    with_ref(ob, what, |ob, what| {
+                        if name == "my" {
+                            ob.my(&who, &what)
+                        } else {
+                            ob.my_(&who, &what)
+                        }
+                    })
+                })
+            }
+            _ => with_ref(ob, who, |ob, obj| match name {
+                "the" => ob.the(&obj),
+                "the_" => ob.the_(&obj),
+                "a" => ob.a(&obj),
+                "a_" => ob.a_(&obj),
+                "thes" => ob.thes(&obj),
+                "thes_" => ob.thes_(&obj),
+                "he" => ob.he(&obj),
+                "he_s" => ob.he_s(&obj),
+                "he_has" => ob.he_has(&obj),
+                "his" => ob.his(&obj),
+                "hiss" => ob.hiss(&obj),
+                "him" => ob.him(&obj),
+                "himself" => ob.himself(&obj),
+                "is" => ob.is(&obj),
+                "has" => ob.has(&obj),
+                "v" => ob.v(&obj, &args[1]),
+                "v_not" => ob.v_not(&obj, &args[1]),
+                "ask" => ob.ask(&obj, &args[1]),
+                "ask_not" => ob.ask_not(&obj, &args[1]),
+                _ => ob,
+            }),
+        }
+    }
+}
+
+impl Template for TemplateText {
+    fn render(&self, ctx: &dyn Context, out: &mut dyn Output) {
+        if self.has_bad_count(ctx) {
+            return;
+        }
+        self.build(ctx, out.out());
+    }
+}
+
+/// A Template made from a closure, see `from_fn`.
+pub struct FnTemplate<F> {
+    f: F,
+}
+
+/// Creates a Template that renders by calling f with
+/// the Context and an OutputBuilder for the Output.
+pub fn from_fn<F>(f: F) -> FnTemplate<F>
+where
+    F: for<'a> Fn(&dyn Context, OutputBuilder<'a>) -> OutputBuilder<'a>,
+{
+    FnTemplate { f }
+}
+
+impl<F> Template for FnTemplate<F>
+where
+    F: for<'a> Fn(&dyn Context, OutputBuilder<'a>) -> OutputBuilder<'a>,
+{
+    fn render(&self, ctx: &dyn Context, out: &mut dyn Output) {
+        (self.f)(ctx, out.out());
+    }
+}
+
+/// The object called name in ctx, "me", "obj", "env" or
+/// any other name given to `Context::get`.
+pub(crate) fn lookup<'c>(ctx: &'c dyn Context, name: &str) -> ObjectRef<'c> {
+    match name {
+        "me" => ctx.get_me(),
+        "obj" => ctx.get_obj(),
+        "env" => ctx.get_env(),
+        _ => ctx.get(name),
+    }
+}
+
+/// Calls f with the object that r refers to.
+/// Counted objects become an ObjectGroup and
+/// numbers and strings are written as they are.
+fn with_ref<'a, F>(ob: OutputBuilder<'a>, r: ObjectRef<'_>, f: F) -> OutputBuilder<'a>
+where
+    F: FnOnce(OutputBuilder<'a>, &dyn Object) -> OutputBuilder<'a>,
+{
+    match r {
+        ObjectRef::Object(obj) => f(ob, obj),
+        ObjectRef::CountedObject(1, obj) => f(ob, obj),
+        ObjectRef::CountedObject(n, obj) => {
+            match ObjectGroup::counted(usize::try_from(n).unwrap_or(0), obj) {
+                Some(group) => f(ob, &group),
+                None => ob,
+            }
+        }
+        ObjectRef::Int(n) => ob.s(&n.to_string()),
+        ObjectRef::String(s) => ob.s(s),
+    }
 }
 
-/*
 #[cfg(test)]
 mod tests {
+    use super::*;
 
     #[test]
     fn test_parse_template() {
+        let t = TemplateText::parse("\\The(me) give\\s(me) \\a(obj) to \\the_(env).").unwrap();
+        assert_eq!(t.parts.len(), 8);
+        assert!(TemplateText::parse("\\\\ a backslash").is_ok());
+        assert!(TemplateText::parse("plain text").is_ok());
+        assert!(TemplateText::parse("\\style(bold)bold\\style()").is_ok());
+        let registry = Registry::new().with("blink");
+        assert!(TemplateText::parse_with("\\style(blink)", &registry).is_ok());
+        assert!(TemplateText::parse_with("\\style(bold)", &registry).is_err());
+    }
+
+    #[test]
+    fn test_parse_bad_template() {
+        for text in &[
+            "\\The me",
+            "\\The(me",
+            "\\unknown(me)",
+            "\\the()",
+            "\\the(me, obj)",
+            "\\v(me)",
+            "\\style(blink)",
+            "\\style(bold, red)",
+        ] {
+            assert!(TemplateText::parse(text).is_err(), "{:?}", text);
+        }
     }
 }
-    */
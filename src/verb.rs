@@ -0,0 +1,772 @@
+//! Conjugation of English verbs.
+
+use std::sync::OnceLock;
+
+use crate::suffix::Suffix;
+use crate::{is_vowel, last_char};
+
+/// The person and number of a verb's subject.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Person {
+    /// I
+    First,
+    /// you
+    Second,
+    /// he, she, it or a singular noun.
+    ThirdSingular,
+    /// they or a plural noun.
+    Plural,
+}
+
+/// The tense verbs are conjugated in.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Tense {
+    /// "gives"
+    Present,
+    /// "gave"
+    Past,
+    /// "will give"
+    Future,
+    /// "is giving"
+    PresentProgressive,
+    /// "was giving"
+    PastProgressive,
+    /// "will be giving"
+    FutureProgressive,
+}
+
+impl Tense {
+    /// The tense without the progressive aspect.
+    pub fn simple(self) -> Self {
+        match self {
+            Tense::PresentProgressive => Tense::Present,
+            Tense::PastProgressive => Tense::Past,
+            Tense::FutureProgressive => Tense::Future,
+            tense => tense,
+        }
+    }
+}
+
+/// Verbs that are not conjugated by adding s/es/ies.
+/// (infinitive, third person singular, other persons)
+const IRREGULAR_PRESENT: &[(&str, &str, &str)] = &[
+    ("be", "is", "are"),
+    ("have", "has", "have"),
+    ("can", "can", "can"),
+    ("could", "could", "could"),
+    ("may", "may", "may"),
+    ("might", "might", "might"),
+    ("must", "must", "must"),
+    ("shall", "shall", "shall"),
+    ("should", "should", "should"),
+    ("will", "will", "will"),
+    ("would", "would", "would"),
+    ("ought", "ought", "ought"),
+];
+
+/// Modal verbs in past and future tense.
+/// (infinitive, past, future)
+const MODALS: &[(&str, &str, &str)] = &[
+    ("can", "could", "will be able to"),
+    ("could", "could", "could"),
+    ("may", "might", "may"),
+    ("might", "might", "might"),
+    ("must", "had to", "will have to"),
+    ("shall", "should", "shall"),
+    ("should", "should", "should"),
+    ("will", "would", "will"),
+    ("would", "would", "would"),
+    ("ought", "ought", "ought"),
+];
+
+/// Verbs that don't get -ed in past tense.
+/// (infinitive, past, past participle)
+const IRREGULAR_PAST: &[(&str, &str, &str)] = &[
+    ("arise", "arose", "arisen"),
+    ("awake", "awoke", "awoken"),
+    ("be", "was", "been"),
+    ("bear", "bore", "borne"),
+    ("beat", "beat", "beaten"),
+    ("become", "became", "become"),
+    ("begin", "began", "begun"),
+    ("bend", "bent", "bent"),
+    ("bet", "bet", "bet"),
+    ("bind", "bound", "bound"),
+    ("bite", "bit", "bitten"),
+    ("bleed", "bled", "bled"),
+    ("blow", "blew", "blown"),
+    ("break", "broke", "broken"),
+    ("breed", "bred", "bred"),
+    ("bring", "brought", "brought"),
+    ("build", "built", "built"),
+    ("burn", "burnt", "burnt"),
+    ("buy", "bought", "bought"),
+    ("cast", "cast", "cast"),
+    ("catch", "caught", "caught"),
+    ("choose", "chose", "chosen"),
+    ("cling", "clung", "clung"),
+    ("come", "came", "come"),
+    ("cost", "cost", "cost"),
+    ("creep", "crept", "crept"),
+    ("cut", "cut", "cut"),
+    ("deal", "dealt", "dealt"),
+    ("dig", "dug", "dug"),
+    ("do", "did", "done"),
+    ("draw", "drew", "drawn"),
+    ("dream", "dreamt", "dreamt"),
+    ("drink", "drank", "drunk"),
+    ("drive", "drove", "driven"),
+    ("eat", "ate", "eaten"),
+    ("fall", "fell", "fallen"),
+    ("feed", "fed", "fed"),
+    ("feel", "felt", "felt"),
+    ("fight", "fought", "fought"),
+    ("find", "found", "found"),
+    ("flee", "fled", "fled"),
+    ("fling", "flung", "flung"),
+    ("fly", "flew", "flown"),
+    ("forbid", "forbade", "forbidden"),
+    ("forget", "forgot", "forgotten"),
+    ("forgive", "forgave", "forgiven"),
+    ("freeze", "froze", "frozen"),
+    ("get", "got", "got"),
+    ("give", "gave", "given"),
+    ("go", "went", "gone"),
+    ("grind", "ground", "ground"),
+    ("grow", "grew", "grown"),
+    ("hang", "hung", "hung"),
+    ("have", "had", "had"),
+    ("hear", "heard", "heard"),
+    ("hide", "hid", "hidden"),
+    ("hit", "hit", "hit"),
+    ("hold", "held", "held"),
+    ("hurt", "hurt", "hurt"),
+    ("keep", "kept", "kept"),
+    ("kneel", "knelt", "knelt"),
+    ("know", "knew", "known"),
+    ("lay", "laid", "laid"),
+    ("lead", "led", "led"),
+    ("leap", "leapt", "leapt"),
+    ("leave", "left", "left"),
+    ("lend", "lent", "lent"),
+    ("let", "let", "let"),
+    ("lie", "lay", "lain"),
+    ("light", "lit", "lit"),
+    ("lose", "lost", "lost"),
+    ("make", "made", "made"),
+    ("mean", "meant", "meant"),
+    ("meet", "met", "met"),
+    ("pay", "paid", "paid"),
+    ("put", "put", "put"),
+    ("quit", "quit", "quit"),
+    ("read", "read", "read"),
+    ("ride", "rode", "ridden"),
+    ("ring", "rang", "rung"),
+    ("rise", "rose", "risen"),
+    ("run", "ran", "run"),
+    ("say", "said", "said"),
+    ("see", "saw", "seen"),
+    ("seek", "sought", "sought"),
+    ("sell", "sold", "sold"),
+    ("send", "sent", "sent"),
+    ("set", "set", "set"),
+    ("shake", "shook", "shaken"),
+    ("shine", "shone", "shone"),
+    ("shoot", "shot", "shot"),
+    ("show", "showed", "shown"),
+    ("shrink", "shrank", "shrunk"),
+    ("shut", "shut", "shut"),
+    ("sing", "sang", "sung"),
+    ("sink", "sank", "sunk"),
+    ("sit", "sat", "sat"),
+    ("slay", "slew", "slain"),
+    ("sleep", "slept", "slept"),
+    ("slide", "slid", "slid"),
+    ("sling", "slung", "slung"),
+    ("smell", "smelt", "smelt"),
+    ("speak", "spoke", "spoken"),
+    ("spend", "spent", "spent"),
+    ("spin", "spun", "spun"),
+    ("spit", "spat", "spat"),
+    ("split", "split", "split"),
+    ("spread", "spread", "spread"),
+    ("spring", "sprang", "sprung"),
+    ("stand", "stood", "stood"),
+    ("steal", "stole", "stolen"),
+    ("stick", "stuck", "stuck"),
+    ("sting", "stung", "stung"),
+    ("stink", "stank", "stunk"),
+    ("strike", "struck", "struck"),
+    ("swear", "swore", "sworn"),
+    ("sweep", "swept", "swept"),
+    ("swim", "swam", "swum"),
+    ("swing", "swung", "swung"),
+    ("take", "took", "taken"),
+    ("teach", "taught", "taught"),
+    ("tear", "tore", "torn"),
+    ("tell", "told", "told"),
+    ("think", "thought", "thought"),
+    ("throw", "threw", "thrown"),
+    ("tread", "trod", "trodden"),
+    ("understand", "understood", "understood"),
+    ("wake", "woke", "woken"),
+    ("wear", "wore", "worn"),
+    ("weave", "wove", "woven"),
+    ("weep", "wept", "wept"),
+    ("win", "won", "won"),
+    ("wind", "wound", "wound"),
+    ("write", "wrote", "written"),
+];
+
+/// Adds s/es/ies to the end of str.
+pub(crate) fn add_s(str: &mut String) {
+    let mut add: &str = "";
+    let mut uc = false;
+    let mut remove = 0;
+
+    {
+        let mut ci = str.chars().rev();
+        if let Some(ch) = ci.next() {
+            if ch.is_uppercase() {
+                uc = true;
+            }
+            add = match ch {
+                's' | 'o' | 'z' | 'x' | 'S' | 'O' | 'Z' | 'X' => "es",
+                'y' | 'Y' => {
+                    remove = 1;
+                    if let Some(c2) = ci.next() {
+                        if is_vowel(c2) {
+                            remove = 0;
+                            "s"
+                        } else {
+                            "ies"
+                        }
+                    } else {
+                        "ies"
+                    }
+                }
+                'h' | 'H' => {
+                    if let Some(c2) = ci.next() {
+                        if c2 == 'c' || c2 == 's' || c2 == 'C' || c2 == 'S' {
+                            "es"
+                        } else {
+                            "s"
+                        }
+                    } else {
+                        "s"
+                    }
+                }
+                _ => "s",
+            }
+        }
+    }
+    while remove > 0 {
+        str.pop();
+        remove -= 1;
+    }
+    if uc {
+        str.push_str(&add.to_uppercase());
+    } else {
+        str.push_str(add);
+    }
+}
+
+/// Gives word the same case as model.
+/// All uppercase models gives all uppercase words,
+/// a leading uppercase letter gives a leading uppercase letter.
+fn match_case(word: &str, model: &str) -> String {
+    let mut letters = model.chars().filter(|c| c.is_alphabetic());
+    let first_upper = letters.next().is_some_and(|c| c.is_uppercase());
+    let mut rest = letters.peekable();
+    if first_upper && rest.peek().is_some() && rest.all(|c| c.is_uppercase()) {
+        word.to_uppercase()
+    } else if first_upper {
+        let mut ret = String::new();
+        crate::uppercase_first_char(word, &mut ret);
+        ret
+    } else {
+        String::from(word)
+    }
+}
+
+/// Splits "pick up" into "pick" and " up".
+fn split_verb(verb: &str) -> (&str, &str) {
+    match verb.find(' ') {
+        Some(pos) => verb.split_at(pos),
+        None => (verb, ""),
+    }
+}
+
+/// Conjugates verb in present tense.
+/// Only the first word is conjugated, so "pick up" becomes "picks up".
+pub fn present(verb: &str, person: Person) -> String {
+    let (word, rest) = split_verb(verb);
+    let lower = word.to_lowercase();
+    let mut ret = if lower == "be" && person == Person::First {
+        match_case("am", word)
+    } else if let Some(irr) = IRREGULAR_PRESENT.iter().find(|irr| irr.0 == lower) {
+        match person {
+            Person::ThirdSingular => match_case(irr.1, word),
+            _ => match_case(irr.2, word),
+        }
+    } else {
+        let mut ret = String::from(word);
+        if person == Person::ThirdSingular {
+            add_s(&mut ret);
+        }
+        ret
+    };
+    ret.push_str(rest);
+    ret
+}
+
+/// Suffix rules for the -ed ending, (suffix, replacement).
+/// The first matching suffix is used, so longer suffixes come first.
+const ED_RULES: &[(&str, &str)] = &[
+    ("ay", "ayed"),
+    ("ey", "eyed"),
+    ("oy", "oyed"),
+    ("uy", "uyed"),
+    ("y", "ied"),
+    ("e", "ed"),
+    ("ic", "icked"),
+];
+
+/// Suffix rules for the -ing ending, (suffix, replacement).
+const ING_RULES: &[(&str, &str)] = &[
+    ("ie", "ying"),
+    ("ee", "eeing"),
+    ("oe", "oeing"),
+    ("ye", "yeing"),
+    ("e", "ing"),
+    ("ic", "icking"),
+];
+
+/// Present participles that don't follow the rules.
+const IRREGULAR_ING: &[(&str, &str)] = &[("be", "being"), ("singe", "singeing")];
+
+/// Verbs with more than one syllable that are stressed on the last one,
+/// so their last consonant is doubled (begin -> beginning).
+const STRESSED_LAST: &[&str] = &[
+    "admit", "begin", "commit", "compel", "control", "equip", "expel", "forbid", "forget", "occur",
+    "omit", "patrol", "permit", "prefer", "propel", "rebel", "refer", "regret", "submit",
+    "transfer", "upset",
+];
+
+/// Should the last consonant be doubled before -ed and -ing?
+/// True for one syllable words ending with consonant, vowel, consonant
+/// (stop -> stopped) and for the words in STRESSED_LAST.
+fn doubles_last_consonant(word: &str) -> bool {
+    let chars: Vec<char> = word.chars().collect();
+    // In "quit" the u is not a vowel of its own.
+    let vowels: Vec<bool> = chars
+        .iter()
+        .enumerate()
+        .map(|(i, c)| is_vowel(*c) && !(*c == 'u' && i > 0 && chars[i - 1] == 'q'))
+        .collect();
+    let n = chars.len();
+    if n < 3 || vowels[n - 1] || !vowels[n - 2] || vowels[n - 3] {
+        return false;
+    }
+    if matches!(chars[n - 1], 'w' | 'x' | 'y') {
+        return false;
+    }
+    let syllables = (0..n)
+        .filter(|i| vowels[*i] && (*i == 0 || !vowels[i - 1]))
+        .count();
+    syllables == 1 || STRESSED_LAST.contains(&word)
+}
+
+static ED_SUFFIX: OnceLock<Suffix<&str>> = OnceLock::new();
+static ING_SUFFIX: OnceLock<Suffix<&str>> = OnceLock::new();
+
+/// The Suffix for rules, built the first time it is used.
+fn suffix_rules(
+    cell: &'static OnceLock<Suffix<&'static str>>,
+    rules: &[(&str, &'static str)],
+) -> &'static Suffix<&'static str> {
+    cell.get_or_init(|| {
+        Suffix::new(
+            rules
+                .iter()
+                .map(|(suffix, replacement)| (String::from(*suffix), *replacement))
+                .collect(),
+        )
+    })
+}
+
+/// Adds ending to word, using the suffix rules and
+/// doubling the last consonant when needed.
+fn inflect(word: &str, rules: &Suffix<&str>, ending: &str) -> String {
+    if let Some((suffix, replacement)) = rules.lookup(word) {
+        let mut ret = String::from(&word[..word.len() - suffix.len()]);
+        ret.push_str(replacement);
+        return ret;
+    }
+    let mut ret = String::from(word);
+    if doubles_last_consonant(word) {
+        if let Some(c) = last_char(word) {
+            ret.push(c);
+        }
+    }
+    ret.push_str(ending);
+    ret
+}
+
+fn regular_past(word: &str) -> String {
+    inflect(word, suffix_rules(&ED_SUFFIX, ED_RULES), "ed")
+}
+
+/// Conjugates verb in past tense, "gave" or "were".
+/// Only the first word is conjugated.
+pub fn past(verb: &str, person: Person) -> String {
+    let (word, rest) = split_verb(verb);
+    let lower = word.to_lowercase();
+    let past = if lower == "be" && (person == Person::Second || person == Person::Plural) {
+        String::from("were")
+    } else if let Some(modal) = MODALS.iter().find(|m| m.0 == lower) {
+        String::from(modal.1)
+    } else if let Some(irr) = IRREGULAR_PAST.iter().find(|irr| irr.0 == lower) {
+        String::from(irr.1)
+    } else {
+        regular_past(&lower)
+    };
+    match_case(&past, word) + rest
+}
+
+/// The past participle of verb, "given" or "jumped".
+/// Only the first word is inflected.
+pub fn past_participle(verb: &str) -> String {
+    let (word, rest) = split_verb(verb);
+    let lower = word.to_lowercase();
+    let participle = if let Some(irr) = IRREGULAR_PAST.iter().find(|irr| irr.0 == lower) {
+        String::from(irr.2)
+    } else if MODALS.iter().any(|m| m.0 == lower) {
+        lower
+    } else {
+        regular_past(&lower)
+    };
+    match_case(&participle, word) + rest
+}
+
+/// The present participle of verb, "giving", "running" or "lying".
+/// Only the first word is inflected.
+pub fn present_participle(verb: &str) -> String {
+    let (word, rest) = split_verb(verb);
+    let lower = word.to_lowercase();
+    let participle = if let Some(irr) = IRREGULAR_ING.iter().find(|irr| irr.0 == lower) {
+        String::from(irr.1)
+    } else {
+        inflect(&lower, suffix_rules(&ING_SUFFIX, ING_RULES), "ing")
+    };
+    match_case(&participle, word) + rest
+}
+
+/// Conjugates verb in tense.
+/// Only the first word is conjugated, so "pick up" becomes "picked up".
+pub fn conjugate(verb: &str, person: Person, tense: Tense) -> String {
+    let (word, rest) = split_verb(verb);
+    let lower = word.to_lowercase();
+    let modal = MODALS.iter().find(|m| m.0 == lower);
+    let ret = match (tense, modal) {
+        (Tense::Present, _) | (Tense::PresentProgressive, Some(_)) => return present(verb, person),
+        (Tense::Past, _) | (Tense::PastProgressive, Some(_)) => return past(verb, person),
+        (Tense::Future, Some(modal)) | (Tense::FutureProgressive, Some(modal)) => {
+            String::from(modal.2)
+        }
+        (Tense::Future, None) => format!("will {}", lower),
+        (Tense::PresentProgressive, None) => {
+            format!("{} {}", present("be", person), present_participle(&lower))
+        }
+        (Tense::PastProgressive, None) => {
+            format!("{} {}", past("be", person), present_participle(&lower))
+        }
+        (Tense::FutureProgressive, None) => format!("will be {}", present_participle(&lower)),
+    };
+    match_case(&ret, word) + rest
+}
+
+/// Auxiliary verbs, their contraction with the pronoun
+/// before them and their contracted negation.
+const AUXILIARIES: &[(&str, Option<&str>, Option<&str>)] = &[
+    ("am", Some("'m"), None),
+    ("is", Some("'s"), Some("isn't")),
+    ("are", Some("'re"), Some("aren't")),
+    ("was", None, Some("wasn't")),
+    ("were", None, Some("weren't")),
+    ("do", None, Some("don't")),
+    ("does", None, Some("doesn't")),
+    ("did", None, Some("didn't")),
+    ("can", None, Some("can't")),
+    ("could", None, Some("couldn't")),
+    ("may", None, None),
+    ("might", None, None),
+    ("must", None, Some("mustn't")),
+    ("shall", None, Some("shan't")),
+    ("should", None, Some("shouldn't")),
+    ("will", Some("'ll"), Some("won't")),
+    ("would", Some("'d"), Some("wouldn't")),
+    ("ought", None, Some("oughtn't")),
+    ("have", Some("'ve"), None),
+    ("has", Some("'s"), None),
+    ("had", Some("'d"), None),
+];
+
+/// How an auxiliary verb is contracted.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Contraction {
+    /// With the pronoun before it, "'s" or "'ll". have/has/had are
+    /// only contracted if have is true, "he's" is not used for
+    /// "he has an apple".
+    Pronoun { have: bool },
+    /// Negated, "isn't" or "won't".
+    Not,
+}
+
+/// Contracts the first word of the conjugated verb,
+/// "is giving" becomes "'s giving" or "isn't giving".
+/// None is returned if it can't be contracted.
+pub fn contract(verb: &str, contraction: Contraction) -> Option<String> {
+    let (word, rest) = split_verb(verb);
+    let lower = word.to_lowercase();
+    let aux = AUXILIARIES.iter().find(|a| a.0 == lower)?;
+    let contracted = match contraction {
+        Contraction::Pronoun { have: false } if matches!(aux.0, "have" | "has" | "had") => None,
+        Contraction::Pronoun { .. } => aux.1,
+        Contraction::Not => aux.2,
+    }?;
+    Some(format!("{}{}", match_case(contracted, word), rest))
+}
+
+/// Splits the conjugated verb into its auxiliary and the rest,
+/// adding do/does/did when needed.
+/// The auxiliary is what is negated and what comes first in questions,
+/// so "gives" becomes ("does", "give") and "is giving" ("is", "giving").
+/// "have" is treated as a main verb, "doesn't have".
+pub fn split_auxiliary(verb: &str, person: Person, tense: Tense) -> (String, String) {
+    let (word, rest) = split_verb(verb);
+    let lower = word.to_lowercase();
+    let is_auxiliary = lower == "be" || MODALS.iter().any(|m| m.0 == lower);
+    let (aux, main) = if lower == "must" && tense.simple() == Tense::Past {
+        (past("do", person), String::from("have to"))
+    } else if is_auxiliary || (tense != Tense::Present && tense != Tense::Past) {
+        let conjugated = conjugate(&lower, person, tense);
+        let (aux, main) = split_verb(&conjugated);
+        (String::from(aux), String::from(main.trim_start()))
+    } else if tense == Tense::Present {
+        (present("do", person), lower)
+    } else {
+        (past("do", person), lower)
+    };
+    let main = format!("{}{}", main, rest);
+    (match_case(&aux, word), String::from(main.trim_start()))
+}
+
+/// Negates an auxiliary verb, "does" becomes "does not" or "doesn't".
+pub fn negate_auxiliary(aux: &str, contract: bool) -> String {
+    match self::contract(aux, Contraction::Not) {
+        Some(contracted) if contract => contracted,
+        _ if aux.to_lowercase() == "can" => match_case("cannot", aux),
+        _ => format!("{} not", aux),
+    }
+}
+
+/// Conjugates the negated verb in tense, "does not give" or "doesn't give".
+pub fn negate(verb: &str, person: Person, tense: Tense, contract: bool) -> String {
+    let (aux, main) = split_auxiliary(verb, person, tense);
+    let mut ret = negate_auxiliary(&aux, contract);
+    if !main.is_empty() {
+        ret.push(' ');
+        ret.push_str(&main);
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_present() {
+        for (verb, third, other) in &[
+            ("jump", "jumps", "jump"),
+            ("kiss", "kisses", "kiss"),
+            ("pry", "pries", "pry"),
+            ("go", "goes", "go"),
+            ("do", "does", "do"),
+            ("be", "is", "are"),
+            ("have", "has", "have"),
+            ("can", "can", "can"),
+            ("pick up", "picks up", "pick up"),
+            ("Be", "Is", "Are"),
+            ("BE", "IS", "ARE"),
+            ("SCREAM", "SCREAMS", "SCREAM"),
+        ] {
+            assert_eq!(present(verb, Person::ThirdSingular), *third);
+            assert_eq!(present(verb, Person::Second), *other);
+            assert_eq!(present(verb, Person::Plural), *other);
+        }
+    }
+
+    #[test]
+    fn test_past() {
+        for (verb, third, other, participle) in &[
+            ("jump", "jumped", "jumped", "jumped"),
+            ("love", "loved", "loved", "loved"),
+            ("pry", "pried", "pried", "pried"),
+            ("play", "played", "played", "played"),
+            ("give", "gave", "gave", "given"),
+            ("be", "was", "were", "been"),
+            ("have", "had", "had", "had"),
+            ("can", "could", "could", "can"),
+            ("stop", "stopped", "stopped", "stopped"),
+            ("drop", "dropped", "dropped", "dropped"),
+            ("die", "died", "died", "died"),
+            ("visit", "visited", "visited", "visited"),
+            ("prefer", "preferred", "preferred", "preferred"),
+            ("pick up", "picked up", "picked up", "picked up"),
+            ("Go", "Went", "Went", "Gone"),
+        ] {
+            assert_eq!(past(verb, Person::ThirdSingular), *third);
+            assert_eq!(past(verb, Person::Second), *other);
+            assert_eq!(past_participle(verb), *participle);
+        }
+    }
+
+    #[test]
+    fn test_present_participle() {
+        for (verb, participle) in &[
+            ("jump", "jumping"),
+            ("give", "giving"),
+            ("be", "being"),
+            ("see", "seeing"),
+            ("dye", "dyeing"),
+            ("lie", "lying"),
+            ("run", "running"),
+            ("stop", "stopping"),
+            ("quit", "quitting"),
+            ("begin", "beginning"),
+            ("visit", "visiting"),
+            ("open", "opening"),
+            ("fix", "fixing"),
+            ("eat", "eating"),
+            ("panic", "panicking"),
+            ("Run", "Running"),
+            ("pick up", "picking up"),
+        ] {
+            assert_eq!(present_participle(verb), *participle);
+        }
+    }
+
+    #[test]
+    fn test_conjugate() {
+        let third = Person::ThirdSingular;
+        assert_eq!(conjugate("give", third, Tense::Present), "gives");
+        assert_eq!(conjugate("give", third, Tense::Past), "gave");
+        assert_eq!(conjugate("give", third, Tense::Future), "will give");
+        assert_eq!(
+            conjugate("give", third, Tense::PresentProgressive),
+            "is giving"
+        );
+        assert_eq!(
+            conjugate("give", Person::Plural, Tense::PastProgressive),
+            "were giving"
+        );
+        assert_eq!(
+            conjugate("give", third, Tense::FutureProgressive),
+            "will be giving"
+        );
+        assert_eq!(conjugate("Be", Person::Second, Tense::Past), "Were");
+        assert_eq!(conjugate("Give", third, Tense::Future), "Will give");
+        assert_eq!(conjugate("can", third, Tense::Future), "will be able to");
+        assert_eq!(conjugate("can", third, Tense::PastProgressive), "could");
+        assert_eq!(
+            conjugate("pick up", third, Tense::PresentProgressive),
+            "is picking up"
+        );
+    }
+
+    #[test]
+    fn test_first_person() {
+        let first = Person::First;
+        assert_eq!(present("be", first), "am");
+        assert_eq!(present("give", first), "give");
+        assert_eq!(past("be", first), "was");
+        assert_eq!(
+            conjugate("go", first, Tense::PresentProgressive),
+            "am going"
+        );
+        assert_eq!(negate("be", first, Tense::Present, true), "am not");
+        assert_eq!(negate("see", first, Tense::Present, true), "don't see");
+    }
+
+    #[test]
+    fn test_contract() {
+        let pronoun = Contraction::Pronoun { have: false };
+        let pronoun_have = Contraction::Pronoun { have: true };
+        assert_eq!(contract("is giving", pronoun).unwrap(), "'s giving");
+        assert_eq!(contract("are", pronoun).unwrap(), "'re");
+        assert_eq!(contract("will be", pronoun).unwrap(), "'ll be");
+        assert_eq!(contract("am not", pronoun).unwrap(), "'m not");
+        assert_eq!(contract("have", pronoun_have).unwrap(), "'ve");
+        assert!(contract("have", pronoun).is_none());
+        assert!(contract("gives", pronoun_have).is_none());
+        assert!(contract("isn't", pronoun_have).is_none());
+        assert_eq!(
+            contract("is giving", Contraction::Not).unwrap(),
+            "isn't giving"
+        );
+        assert_eq!(contract("Will", Contraction::Not).unwrap(), "Won't");
+        assert!(contract("am", Contraction::Not).is_none());
+        assert!(contract("have", Contraction::Not).is_none());
+    }
+
+    #[test]
+    fn test_split_auxiliary() {
+        let third = Person::ThirdSingular;
+        let split = |verb, person, tense| {
+            let (aux, main) = split_auxiliary(verb, person, tense);
+            format!("{}|{}", aux, main)
+        };
+        assert_eq!(split("give", third, Tense::Present), "does|give");
+        assert_eq!(split("give", Person::Second, Tense::Present), "do|give");
+        assert_eq!(split("Give", third, Tense::Past), "Did|give");
+        assert_eq!(split("pick up", third, Tense::Past), "did|pick up");
+        assert_eq!(split("be", third, Tense::Present), "is|");
+        assert_eq!(split("be", Person::Plural, Tense::Past), "were|");
+        assert_eq!(split("have", third, Tense::Present), "does|have");
+        assert_eq!(split("can", third, Tense::Present), "can|");
+        assert_eq!(split("must", third, Tense::Past), "did|have to");
+        assert_eq!(split("give", third, Tense::Future), "will|give");
+        assert_eq!(split("give", third, Tense::PresentProgressive), "is|giving");
+    }
+
+    #[test]
+    fn test_negate() {
+        let third = Person::ThirdSingular;
+        assert_eq!(negate("see", third, Tense::Present, true), "doesn't see");
+        assert_eq!(negate("see", third, Tense::Present, false), "does not see");
+        assert_eq!(
+            negate("have", Person::Second, Tense::Present, true),
+            "don't have"
+        );
+        assert_eq!(negate("be", Person::Plural, Tense::Past, true), "weren't");
+        assert_eq!(negate("can", third, Tense::Present, false), "cannot");
+        assert_eq!(negate("will", third, Tense::Present, true), "won't");
+        assert_eq!(negate("may", third, Tense::Present, true), "may not");
+        assert_eq!(negate("give", third, Tense::Future, true), "won't give");
+        assert_eq!(
+            negate("give", third, Tense::PastProgressive, false),
+            "was not giving"
+        );
+        assert_eq!(negate("Eat", third, Tense::Past, true), "Didn't eat");
+    }
+
+    #[test]
+    fn test_match_case() {
+        assert_eq!(match_case("is", "be"), "is");
+        assert_eq!(match_case("is", "Be"), "Is");
+        assert_eq!(match_case("is", "BE"), "IS");
+        assert_eq!(match_case("is", "B"), "Is");
+    }
+}
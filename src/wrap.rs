@@ -0,0 +1,221 @@
+//! An Output adapter that wraps lines.
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::verb::Tense;
+use crate::{Mention, Narration, Object, Output, OutputBuilder, Viewer};
+
+/// What was written to the WrapOutput.
+enum Event {
+    Word(String),
+    Space,
+    Newline,
+    Style(String),
+    BeginStyle(String),
+    EndStyle(String, Vec<String>),
+    BeginObject(Mention),
+    EndObject,
+}
+
+/// An Output that wraps the text at word boundaries before
+/// it is written to the inner Output.
+///
+/// The width is measured in display columns, styles are not counted.
+/// A style that starts right before a word is moved to the next line
+/// together with the word.
+/// Lines after the first can be indented, for lists.
+/// Words wider than the line are not broken and
+/// spaces between words are written as one space.
+/// Everything, but the wrapping, is answered by the inner Output.
+pub struct WrapOutput<O: Output> {
+    inner: O,
+    width: usize,
+    indent: usize,
+    events: Vec<Event>,
+}
+
+impl<O: Output> WrapOutput<O> {
+    /// Creates a WrapOutput that wraps lines longer than width.
+    /// A width of zero means no wrapping.
+    pub fn new(inner: O, width: usize) -> Self {
+        Self {
+            inner,
+            width,
+            indent: 0,
+            events: vec![],
+        }
+    }
+
+    /// Indents the lines after the first with indent spaces.
+    pub fn with_indent(mut self, indent: usize) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    pub fn set_width(&mut self, width: usize) {
+        self.width = width;
+    }
+
+    pub fn set_indent(&mut self, indent: usize) {
+        self.indent = indent;
+    }
+
+    pub fn inner(&self) -> &O {
+        &self.inner
+    }
+
+    pub fn inner_mut(&mut self) -> &mut O {
+        &mut self.inner
+    }
+
+    /// Returns the inner Output.
+    pub fn into_inner(self) -> O {
+        self.inner
+    }
+
+    fn write_event(&mut self, event: &Event) {
+        match event {
+            Event::Word(word) => self.inner.write_text(word),
+            Event::Space => self.inner.write_text(" "),
+            Event::Newline => self.inner.write_text("\n"),
+            Event::Style(style) => self.inner.write_style(style),
+            Event::BeginStyle(style) => self.inner.begin_style(style),
+            Event::EndStyle(style, active) => self.inner.end_style(style, active),
+            Event::BeginObject(mention) => self.inner.begin_object(mention),
+            Event::EndObject => self.inner.end_object(),
+        }
+    }
+
+    /// Writes the events, with line breaks instead of spaces where needed.
+    fn write_lines(&mut self) {
+        let events = std::mem::take(&mut self.events);
+        // The word being collected, with the styles in it.
+        let mut word: Vec<&Event> = vec![];
+        let mut word_width = 0;
+        let mut column = 0;
+        let mut space = false;
+        for event in events.iter().chain(std::iter::once(&Event::Space)) {
+            match event {
+                Event::Word(text) => {
+                    word_width += text.width();
+                    word.push(event);
+                }
+                Event::Style(_)
+                | Event::BeginStyle(_)
+                | Event::EndStyle(_, _)
+                | Event::BeginObject(_)
+                | Event::EndObject => word.push(event),
+                Event::Space | Event::Newline => {
+                    let has_text = word.iter().any(|e| matches!(e, Event::Word(_)));
+                    if has_text {
+                        if self.width > 0 && column > 0 && column + 1 + word_width > self.width {
+                            self.write_event(&Event::Newline);
+                            self.inner.write_text(&" ".repeat(self.indent));
+                            column = self.indent;
+                        } else if space {
+                            self.write_event(&Event::Space);
+                            column += 1;
+                        }
+                        column += word_width;
+                        space = false;
+                    }
+                    for e in word.drain(..) {
+                        self.write_event(e);
+                    }
+                    word_width = 0;
+                    if let Event::Newline = event {
+                        self.write_event(event);
+                        column = 0;
+                        space = false;
+                    } else if has_text || column > 0 {
+                        space = true;
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<O: Output> Output for WrapOutput<O> {
+    fn write_text(&mut self, text: &str) {
+        let mut word = String::new();
+        for c in text.chars() {
+            if c.is_whitespace() {
+                if !word.is_empty() {
+                    self.events.push(Event::Word(std::mem::take(&mut word)));
+                }
+                self.events.push(if c == '\n' {
+                    Event::Newline
+                } else {
+                    Event::Space
+                });
+            } else {
+                word.push(c);
+            }
+        }
+        if !word.is_empty() {
+            self.events.push(Event::Word(word));
+        }
+    }
+
+    fn write_style(&mut self, style: &str) {
+        self.events.push(Event::Style(String::from(style)));
+    }
+
+    fn begin_style(&mut self, style: &str) {
+        self.events.push(Event::BeginStyle(String::from(style)));
+    }
+
+    fn end_style(&mut self, style: &str, active: &[String]) {
+        self.events
+            .push(Event::EndStyle(String::from(style), active.to_vec()));
+    }
+
+    fn begin_object(&mut self, mention: &Mention) {
+        self.events.push(Event::BeginObject(*mention));
+    }
+
+    fn end_object(&mut self) {
+        self.events.push(Event::EndObject);
+    }
+
+    /// Writes the wrapped text to the inner Output and calls its done().
+    fn done(&mut self) {
+        self.write_lines();
+        self.inner.done();
+    }
+
+    fn out(&mut self) -> OutputBuilder<'_> {
+        OutputBuilder::new(self as &mut dyn Output)
+    }
+
+    fn tense(&self) -> Tense {
+        self.inner.tense()
+    }
+
+    fn contractions(&self) -> bool {
+        self.inner.contractions()
+    }
+
+    fn narration(&self) -> Narration {
+        self.inner.narration()
+    }
+}
+
+impl<O: Output> Viewer for WrapOutput<O> {
+    fn can_see(&self, who: &dyn Object) -> bool {
+        self.inner.can_see(who)
+    }
+
+    fn can(&self, verb: &str, who: &dyn Object) -> bool {
+        self.inner.can(verb, who)
+    }
+
+    fn has(&self, property: &str) -> bool {
+        self.inner.has(property)
+    }
+
+    fn is_me(&self, who: &dyn Object) -> bool {
+        self.inner.is_me(who)
+    }
+}
@@ -1,8 +1,11 @@
-use langgen;
-use std;
+use langgen::templates::{Context, ObjectRef};
 use langgen::*;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
 
 pub struct DebugObject {
+    id: u64,
     named: Box<dyn Named>,
 }
 
@@ -13,8 +16,13 @@ impl DebugObject {
 
         let mut buff = std::io::Cursor::new("man:men\n");
         let nf = Factory::from_reader(&mut buff).unwrap();
+        Self::from_named(nf.create(name, sex, thing).unwrap())
+    }
+
+    pub fn from_named(named: Box<dyn Named>) -> Self {
         Self {
-            named: nf.create(name, sex, thing),
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            named,
         }
     }
 
@@ -47,7 +55,11 @@ impl DebugObject {
     }
 }
 
-impl Object for DebugObject {}
+impl Object for DebugObject {
+    fn id(&self) -> u64 {
+        self.id
+    }
+}
 
 impl Named for DebugObject {
     fn gender(&self) -> Gender {
@@ -89,6 +101,14 @@ impl Named for DebugObject {
     fn long_plural_name(&self) -> &str {
         self.named.long_plural_name()
     }
+
+    fn aliases(&self) -> &[String] {
+        self.named.aliases()
+    }
+
+    fn unseen_name(&self) -> Option<&str> {
+        self.named.unseen_name()
+    }
 }
 
 #[allow(dead_code)]
@@ -96,17 +116,28 @@ pub struct DebugOutput {
     pub text: String,
     pub last_text: String,
     pub can_see: bool,
+    /// If set, only the objects with these ids can be seen.
+    pub seen_ids: Option<Vec<u64>>,
     pub me: bool,
+    pub me_id: Option<u64>,
+    pub tense: verb::Tense,
+    pub contractions: bool,
+    pub narration: Narration,
+    /// The verbs `can` answers false for, like "hear".
+    pub cannot: Vec<&'static str>,
 }
 
 impl crate::Viewer for DebugOutput {
-    fn can_see(&self, _who: &dyn Object) -> bool {
-        self.can_see
+    fn can_see(&self, who: &dyn Object) -> bool {
+        match &self.seen_ids {
+            Some(ids) => ids.contains(&who.id()),
+            None => self.can_see,
+        }
     }
 
     // Ie the viewer can "hear" Object.
-    fn can(&self, _verb: &str, _who: &dyn Object) -> bool {
-        true
+    fn can(&self, verb: &str, _who: &dyn Object) -> bool {
+        !self.cannot.contains(&verb)
     }
 
     // Ie The viewer has the see_curse property?
@@ -114,8 +145,8 @@ impl crate::Viewer for DebugOutput {
         true
     }
 
-    fn is_me(&self, _who: &dyn Object) -> bool {
-        self.me
+    fn is_me(&self, who: &dyn Object) -> bool {
+        self.me || self.me_id == Some(who.id())
     }
 }
 
@@ -139,6 +170,18 @@ impl Output for DebugOutput {
     fn out(&mut self) -> OutputBuilder<'_> {
         OutputBuilder::new(self as &mut dyn Output)
     }
+
+    fn tense(&self) -> verb::Tense {
+        self.tense
+    }
+
+    fn contractions(&self) -> bool {
+        self.contractions
+    }
+
+    fn narration(&self) -> Narration {
+        self.narration
+    }
 }
 
 #[allow(dead_code)]
@@ -148,7 +191,81 @@ impl DebugOutput {
             text: String::new(),
             last_text: String::new(),
             can_see: true,
+            seen_ids: None,
             me: false,
+            me_id: None,
+            tense: verb::Tense::Present,
+            contractions: false,
+            narration: Narration::Second,
+            cannot: vec![],
+        }
+    }
+}
+
+/// A Context with me, obj, env and num.
+#[allow(dead_code)]
+pub struct DebugContext<'a> {
+    pub me: Option<&'a DebugObject>,
+    pub obj: Option<&'a DebugObject>,
+    pub env: Option<&'a DebugObject>,
+    pub num: i64,
+}
+
+fn object_ref(obj: Option<&DebugObject>) -> ObjectRef<'_> {
+    match obj {
+        Some(obj) => ObjectRef::Object(obj),
+        None => ObjectRef::String("nothing"),
+    }
+}
+
+#[allow(dead_code)]
+impl<'a> DebugContext<'a> {
+    pub fn new(me: &'a DebugObject) -> Self {
+        Self {
+            me: Some(me),
+            obj: None,
+            env: None,
+            num: 0,
+        }
+    }
+
+    pub fn obj(mut self, obj: &'a DebugObject) -> Self {
+        self.obj = Some(obj);
+        self
+    }
+
+    pub fn env(mut self, env: &'a DebugObject) -> Self {
+        self.env = Some(env);
+        self
+    }
+
+    pub fn num(mut self, num: i64) -> Self {
+        self.num = num;
+        self
+    }
+}
+
+impl<'a> Context for DebugContext<'a> {
+    fn get_me(&self) -> ObjectRef<'_> {
+        object_ref(self.me)
+    }
+
+    fn get_obj(&self) -> ObjectRef<'_> {
+        object_ref(self.obj)
+    }
+
+    fn get_env(&self) -> ObjectRef<'_> {
+        object_ref(self.env)
+    }
+
+    fn get(&self, who: &str) -> ObjectRef<'_> {
+        match who {
+            "num" => ObjectRef::Int(self.num),
+            "objs" => match self.obj {
+                Some(obj) => ObjectRef::CountedObject(self.num, obj),
+                None => ObjectRef::String("nothing"),
+            },
+            _ => ObjectRef::String(""),
         }
     }
 }
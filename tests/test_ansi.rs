@@ -0,0 +1,125 @@
+mod common;
+
+use crate::common::*;
+use langgen::ansi::{AnsiOutput, Theme};
+use langgen::*;
+
+fn text(out: AnsiOutput<Vec<u8>, DebugOutput>) -> String {
+    String::from_utf8(out.into_inner()).unwrap()
+}
+
+#[test]
+fn test_plain_text() {
+    let adam = DebugObject::adam();
+    let apple = DebugObject::apple();
+    let mut out = AnsiOutput::new(vec![], DebugOutput::new());
+
+    out.out().the(&adam).v(&adam, "take").the(&apple);
+    out.out().the(&apple).v(&apple, "be").s("green");
+    assert_eq!(text(out), "Adam takes the apple.\nThe apple is green.\n");
+}
+
+#[test]
+fn test_styles() {
+    let mut out = AnsiOutput::new(vec![], DebugOutput::new());
+
+    out.write_style("npc");
+    out.write_text("Adam");
+    out.write_style("");
+    out.write_text(" says ");
+    out.write_style("speech");
+    out.write_text("hello");
+    out.done();
+    assert_eq!(
+        text(out),
+        "\x1b[1;33mAdam\x1b[0m says \x1b[35mhello\x1b[0m\n"
+    );
+}
+
+#[test]
+fn test_theme() {
+    let theme = Theme::new().with("npc", "31");
+    let mut out = AnsiOutput::with_theme(vec![], DebugOutput::new(), theme);
+    assert_eq!(out.theme().get("bold"), None);
+
+    out.write_style("npc");
+    out.write_text("Adam");
+    out.done();
+    out.write_style("bold");
+    out.write_text("Eva");
+    out.done();
+
+    out.theme_mut().set("bold", "1");
+    out.write_style("bold");
+    out.write_text("Eva");
+    out.done();
+    assert_eq!(text(out), "\x1b[31mAdam\x1b[0m\nEva\n\x1b[1mEva\x1b[0m\n");
+}
+
+#[test]
+fn test_viewer() {
+    let adam = DebugObject::adam();
+    let mut viewer = DebugOutput::new();
+    viewer.me_id = Some(adam.id());
+    let mut out = AnsiOutput::new(vec![], viewer);
+
+    out.out().the(&adam).v(&adam, "be").s("hungry");
+    assert_eq!(text(out), "You are hungry.\n");
+}
+
+#[test]
+fn test_nested_styles() {
+    let adam = DebugObject::adam();
+    let mut out = AnsiOutput::new(vec![], DebugOutput::new());
+
+    out.out()
+        .style("speech")
+        .s("hello")
+        .style("npc")
+        .the(&adam)
+        .end_style()
+        .s("and")
+        .style("blink")
+        .s("bye")
+        .end_style();
+    assert_eq!(
+        text(out),
+        "\x1b[35mHello \x1b[1;33mAdam\x1b[0m\x1b[35m and bye\x1b[0m.\n"
+    );
+}
+
+#[test]
+fn test_unknown_styles() {
+    let mut out = AnsiOutput::new(vec![], DebugOutput::new());
+
+    out.write_style("npc");
+    out.write_style("unknown");
+    out.write_text("Adam");
+    out.write_style("");
+    out.write_text(" still");
+    out.write_style("");
+    out.write_text(" normal");
+    out.write_style("");
+    out.done();
+    assert_eq!(text(out), "\x1b[1;33mAdam still\x1b[0m normal\n");
+}
+
+#[test]
+fn test_control_characters() {
+    let mut out = AnsiOutput::new(vec![], DebugOutput::new());
+
+    out.write_text("Adam\x1b[2J says\x07 hi\r\tthere\nbye");
+    out.done();
+    assert_eq!(text(out), "Adam[2J says hi\tthere\nbye\n");
+}
+
+#[test]
+fn test_narration() {
+    let adam = DebugObject::adam();
+    let mut viewer = DebugOutput::new();
+    viewer.me_id = Some(adam.id());
+    let mut out = AnsiOutput::new(vec![], viewer).with_narration(Narration::First);
+
+    out.out().the(&adam).v(&adam, "be").s("hungry");
+    assert_eq!(text(out), "I am hungry.\n");
+}
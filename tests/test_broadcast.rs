@@ -0,0 +1,170 @@
+mod common;
+
+use crate::common::*;
+use langgen::broadcast::{act, act_with, broadcast, broadcast_variants, Act, Role};
+use langgen::templates::{from_fn, Context, Template, TemplateText};
+use langgen::*;
+
+fn viewer(me: Option<&DebugObject>) -> DebugOutput {
+    let mut out = DebugOutput::new();
+    out.me_id = me.map(|me| me.id());
+    out
+}
+
+#[test]
+fn test_broadcast_template() {
+    let adam = DebugObject::adam();
+    let eva = DebugObject::eva();
+    let apple = DebugObject::apple();
+    let template = TemplateText::parse("\\The(me) give\\s(me) \\the(env) \\a(obj).").unwrap();
+    let ctx = DebugContext::new(&adam).obj(&apple).env(&eva);
+    let mut outs = [viewer(Some(&adam)), viewer(Some(&eva)), viewer(None)];
+
+    broadcast(
+        &template,
+        &ctx,
+        outs.iter_mut().map(|o| o as &mut dyn Output),
+    );
+    assert_eq!(outs[0].last_text, "You give Eva an apple.");
+    assert_eq!(outs[1].last_text, "Adam gives you an apple.");
+    assert_eq!(outs[2].last_text, "Adam gives Eva an apple.");
+}
+
+#[test]
+fn test_broadcast_closure() {
+    let adam = DebugObject::adam();
+    let eva = DebugObject::eva();
+    let template = from_fn(|_ctx: &dyn Context, ob| ob.the(&adam).v(&adam, "hug").the(&eva));
+    let ctx = DebugContext::new(&adam);
+    let mut actor = viewer(Some(&adam));
+    let mut target = viewer(Some(&eva));
+    let outs: Vec<&mut dyn Output> = vec![&mut actor, &mut target];
+
+    broadcast(&template, &ctx, outs);
+    assert_eq!(actor.last_text, "You hug Eva.");
+    assert_eq!(target.last_text, "Adam hugs you.");
+}
+
+#[test]
+fn test_broadcast_variants() {
+    let adam = DebugObject::adam();
+    let eva = DebugObject::eva();
+    let whisper = TemplateText::parse("\\The(me) whisper\\s(me) to \\the(env).").unwrap();
+    let secret = TemplateText::parse("\\The(me) tell\\s(me) \\the(env) a secret.").unwrap();
+    let ctx = DebugContext::new(&adam).env(&eva);
+    let mut outs = vec![viewer(Some(&adam)), viewer(Some(&eva)), viewer(None)];
+    let mut blind = viewer(None);
+    blind.can_see = false;
+    blind.last_text = String::from("unchanged");
+    outs.push(blind);
+
+    broadcast_variants(
+        &ctx,
+        outs.iter_mut().map(|o| o as &mut dyn Output),
+        |out| -> Option<&dyn Template> {
+            if out.is_me(&eva) {
+                Some(&secret)
+            } else if out.is_me(&adam) || out.can_see(&adam) {
+                Some(&whisper)
+            } else {
+                None
+            }
+        },
+    );
+    assert_eq!(outs[0].last_text, "You whisper to Eva.");
+    assert_eq!(outs[1].last_text, "Adam tells you a secret.");
+    assert_eq!(outs[2].last_text, "Adam whispers to Eva.");
+    assert_eq!(outs[3].last_text, "unchanged");
+}
+
+fn parse(text: &str) -> TemplateText {
+    TemplateText::parse(text).unwrap()
+}
+
+#[test]
+fn test_act() {
+    let adam = DebugObject::adam();
+    let eva = DebugObject::eva();
+    let to_actor = parse("You poke \\the(obj).");
+    let to_target = parse("\\The(me) pokes you.");
+    let to_others = parse("\\The(me) pokes \\the(obj).");
+    let variants = Act::new(&to_actor, &to_target, &to_others);
+    let ctx = DebugContext::new(&adam).obj(&eva);
+    let mut outs = [viewer(Some(&adam)), viewer(Some(&eva)), viewer(None)];
+
+    act(
+        &variants,
+        &ctx,
+        &adam,
+        Some(&eva),
+        outs.iter_mut().map(|o| o as &mut dyn Output),
+    )
+    .unwrap();
+    assert_eq!(outs[0].last_text, "You poke Eva.");
+    assert_eq!(outs[1].last_text, "Adam pokes you.");
+    assert_eq!(outs[2].last_text, "Adam pokes Eva.");
+}
+
+#[test]
+fn test_act_reflexive() {
+    let adam = DebugObject::adam();
+    let eva = DebugObject::eva();
+    let hug = parse("\\The(me) hug\\s(me) \\the(obj).");
+    let hug_self = parse("\\The(me) hug\\s(me) \\himself(me).");
+    let ctx = DebugContext::new(&adam).obj(&adam);
+    let mut outs = [viewer(Some(&adam)), viewer(Some(&eva))];
+
+    let variants = Act::default().actor(&hug).others(&hug);
+    let err = act(
+        &variants,
+        &ctx,
+        &adam,
+        Some(&adam),
+        outs.iter_mut().map(|o| o as &mut dyn Output),
+    )
+    .unwrap_err();
+    assert!(err.contains("no reflexive template"));
+    assert_eq!(outs[0].last_text, "");
+    assert_eq!(outs[1].last_text, "");
+
+    let variants = variants.reflexive(&hug_self);
+    act(
+        &variants,
+        &ctx,
+        &adam,
+        Some(&adam),
+        outs.iter_mut().map(|o| o as &mut dyn Output),
+    )
+    .unwrap();
+    assert_eq!(outs[0].last_text, "You hug yourself.");
+    assert_eq!(outs[1].last_text, "Adam hugs himself.");
+}
+
+#[test]
+fn test_act_with_roles() {
+    let adam = DebugObject::adam();
+    let eva = DebugObject::eva();
+    let smile = parse("\\The(me) smile\\s(me).");
+    let ctx = DebugContext::new(&adam);
+    let mut outs = [viewer(Some(&adam)), viewer(Some(&eva)), viewer(None)];
+    outs[2].last_text = String::from("unchanged");
+    let mut roles = vec![];
+
+    act_with(
+        &ctx,
+        &adam,
+        Some(&eva),
+        outs.iter_mut().map(|o| o as &mut dyn Output),
+        |role, _| -> Option<&dyn Template> {
+            roles.push(role);
+            match role {
+                Role::Other => None,
+                _ => Some(&smile),
+            }
+        },
+    );
+    assert_eq!(roles, vec![Role::Actor, Role::Target, Role::Other]);
+    assert_eq!(outs[0].last_text, "You smile.");
+    assert_eq!(outs[1].last_text, "Adam smiles.");
+    assert_eq!(outs[2].last_text, "unchanged");
+}
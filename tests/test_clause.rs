@@ -0,0 +1,109 @@
+mod common;
+
+use crate::common::*;
+use langgen::clause::{Clause, Mood, Noun};
+use langgen::*;
+
+#[test]
+fn test_declarative() {
+    let mut out = DebugOutput::new();
+    let adam = DebugObject::adam();
+    let apple = DebugObject::apple();
+    let eva = DebugObject::eva();
+    let clause = Clause::new(Noun::the(&adam), "give")
+        .object(Noun::a(&apple))
+        .prep("to", Noun::the_(&eva));
+
+    clause.render(&mut out);
+    assert_eq!(out.last_text, "Adam gives an apple to Eva Adamsfru.");
+
+    out.me_id = Some(adam.id());
+    clause.render(&mut out);
+    assert_eq!(out.last_text, "You give an apple to Eva Adamsfru.");
+
+    out.me_id = Some(eva.id());
+    clause.render(&mut out);
+    assert_eq!(out.last_text, "Adam gives an apple to you.");
+}
+
+#[test]
+fn test_tense_and_polarity() {
+    let mut out = DebugOutput::new();
+    let adam = DebugObject::adam();
+    let apples = DebugObject::apples();
+
+    Clause::new(Noun::the(&adam), "see")
+        .object(Noun::the(&apples))
+        .negated()
+        .render(&mut out);
+    assert_eq!(out.last_text, "Adam does not see the apples.");
+
+    out.contractions = true;
+    Clause::new(Noun::the(&apples), "fall")
+        .tense(verb::Tense::Past)
+        .negated()
+        .adverb("slowly")
+        .render(&mut out);
+    assert_eq!(out.last_text, "The apples didn't fall slowly.");
+
+    Clause::new(Noun::the(&adam), "eat")
+        .tense(verb::Tense::PresentProgressive)
+        .object(Noun::my(&adam, &apples))
+        .render(&mut out);
+    assert_eq!(out.last_text, "Adam is eating his apples.");
+}
+
+#[test]
+fn test_moods() {
+    let mut out = DebugOutput::new();
+    let adam = DebugObject::adam();
+    let apple = DebugObject::apple();
+    let eva = DebugObject::eva();
+
+    Clause::new(Noun::the(&eva), "want")
+        .mood(Mood::Interrogative)
+        .object(Noun::the(&apple))
+        .render(&mut out);
+    assert_eq!(out.last_text, "Does Eva want the apple?");
+
+    Clause::new(Noun::the(&eva), "be")
+        .mood(Mood::Interrogative)
+        .negated()
+        .adverb("here")
+        .render(&mut out);
+    assert_eq!(out.last_text, "Is Eva not here?");
+
+    out.me_id = Some(adam.id());
+    Clause::new(Noun::the(&adam), "give")
+        .mood(Mood::Imperative)
+        .object(Noun::the(&eva))
+        .object(Noun::the(&apple))
+        .render(&mut out);
+    assert_eq!(out.last_text, "Give Eva the apple.");
+
+    Clause::new(Noun::the(&adam), "eat")
+        .mood(Mood::Imperative)
+        .negated()
+        .object(Noun::the(&apple))
+        .render(&mut out);
+    assert_eq!(out.last_text, "Do not eat the apple.");
+}
+
+#[test]
+fn test_reflexive() {
+    let mut out = DebugOutput::new();
+    let adam = DebugObject::adam();
+    let eva = DebugObject::eva();
+
+    Clause::new(Noun::the(&eva), "look")
+        .prep("at", Noun::the(&eva))
+        .render(&mut out);
+    assert_eq!(out.last_text, "Eva looks at herself.");
+
+    out.me_id = Some(adam.id());
+    Clause::new(Noun::the(&adam), "hurt")
+        .tense(verb::Tense::Past)
+        .object(Noun::the(&adam))
+        .render(&mut out);
+    assert_eq!(out.last_text, "You hurt yourself.");
+}
@@ -0,0 +1,120 @@
+mod common;
+
+use crate::common::*;
+use langgen::html::{Classes, HtmlOutput};
+use langgen::*;
+
+fn text(out: HtmlOutput<Vec<u8>, DebugOutput>) -> String {
+    String::from_utf8(out.into_inner()).unwrap()
+}
+
+#[test]
+fn test_blocks() {
+    let adam = DebugObject::adam();
+    let apple = DebugObject::apple();
+    let mut out = HtmlOutput::new(vec![], DebugOutput::new());
+
+    out.out().the(&adam).v(&adam, "take").the(&apple);
+    out.out().the(&apple).v(&apple, "be").s("green");
+    assert_eq!(
+        text(out),
+        "<p>Adam takes the apple.</p>\n<p>The apple is green.</p>\n"
+    );
+}
+
+#[test]
+fn test_escaped_names() {
+    let evil = DebugObject::new("!<script>alert(1)</script>", Gender::Male, false);
+    let mut out = HtmlOutput::new(vec![], DebugOutput::new());
+
+    out.out().the(&evil).v(&evil, "say").s("\"hi\" & bye");
+    assert_eq!(
+        text(out),
+        "<p>&lt;script&gt;alert(1)&lt;/script&gt; says &quot;hi&quot; &amp; bye.</p>\n"
+    );
+}
+
+#[test]
+fn test_nested_styles() {
+    let mut out = HtmlOutput::new(vec![], DebugOutput::new());
+
+    out.write_style("npc");
+    out.write_text("Adam ");
+    out.write_style("bold");
+    out.write_text("waves");
+    out.write_style("");
+    out.write_style("");
+    out.write_text(".");
+    out.done();
+
+    out.write_style("speech");
+    out.write_style("unknown");
+    out.write_text("Hello");
+    out.write_style("");
+    out.done();
+    assert_eq!(
+        text(out),
+        "<p><span class=\"npc\">Adam <span class=\"bold\">waves</span></span>.</p>\n\
+         <p><span class=\"speech\">Hello</span></p>\n"
+    );
+}
+
+#[test]
+fn test_classes() {
+    let classes = Classes::new().with("npc", "mud-npc \"x\"");
+    let mut out = HtmlOutput::with_classes(vec![], DebugOutput::new(), classes);
+    assert_eq!(out.classes().get("bold"), None);
+
+    out.write_style("npc");
+    out.write_text("Adam");
+    out.done();
+
+    out.classes_mut().set("bold", "b");
+    out.write_style("bold");
+    out.write_text("Eva");
+    out.done();
+    assert_eq!(
+        text(out),
+        "<p><span class=\"mud-npc &quot;x&quot;\">Adam</span></p>\n\
+         <p><span class=\"b\">Eva</span></p>\n"
+    );
+}
+
+#[test]
+fn test_builder_styles() {
+    let adam = DebugObject::adam();
+    let mut out = HtmlOutput::new(vec![], DebugOutput::new());
+
+    out.out()
+        .style("npc")
+        .the(&adam)
+        .end_style()
+        .s("says")
+        .style("speech")
+        .s("<hi>")
+        .end_style();
+    assert_eq!(
+        text(out),
+        "<p><span class=\"npc\">Adam</span> says <span class=\"speech\">&lt;hi&gt;</span>.</p>\n"
+    );
+}
+
+#[test]
+fn test_narration() {
+    let adam = DebugObject::adam();
+    let mut viewer = DebugOutput::new();
+    viewer.me_id = Some(adam.id());
+    let mut out = HtmlOutput::new(vec![], viewer).with_narration(Narration::Third);
+
+    out.out().the(&adam).v(&adam, "be").s("hungry");
+    assert_eq!(text(out), "<p>Adam is hungry.</p>\n");
+}
+
+#[test]
+fn test_contractions() {
+    let adam = DebugObject::adam();
+    let mut out = HtmlOutput::new(vec![], DebugOutput::new()).with_contractions(true);
+
+    out.out().he(&adam).is(&adam).s("hungry");
+    assert_eq!(text(out), "<p>He&#39;s hungry.</p>\n");
+}
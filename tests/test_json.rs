@@ -0,0 +1,149 @@
+#![cfg(feature = "json")]
+
+mod common;
+
+use crate::common::*;
+use langgen::json::{Event, JsonOutput, Segment};
+use langgen::*;
+
+fn events(out: JsonOutput<Vec<u8>, DebugOutput>) -> Vec<Event> {
+    let text = String::from_utf8(out.into_inner()).unwrap();
+    text.lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+fn text(text: &str) -> Segment {
+    Segment {
+        text: String::from(text),
+        styles: vec![],
+        object: None,
+    }
+}
+
+fn object(text: &str, obj: &dyn Object, form: NameForm, seen: bool) -> Segment {
+    Segment {
+        text: String::from(text),
+        styles: vec![],
+        object: Some(Mention {
+            id: obj.id(),
+            form,
+            seen,
+        }),
+    }
+}
+
+#[test]
+fn test_mentions() {
+    let adam = DebugObject::adam();
+    let apple = DebugObject::apple();
+    let mut out = JsonOutput::new(vec![], DebugOutput::new());
+
+    out.out().the(&adam).v(&adam, "take").a_(&apple);
+    out.out().he(&adam).v(&adam, "eat").his(&adam).s("food");
+    let events = events(out);
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].text, "Adam takes a green apple.");
+    assert_eq!(
+        events[0].segments,
+        vec![
+            object("Adam", &adam, NameForm::ShortName, true),
+            text(" takes "),
+            object("a green apple", &apple, NameForm::LongName, true),
+            text("."),
+        ]
+    );
+    assert_eq!(
+        events[1].segments,
+        vec![
+            object("He", &adam, NameForm::Pronoun, true),
+            text(" eats "),
+            object("his", &adam, NameForm::Pronoun, true),
+            text(" food."),
+        ]
+    );
+}
+
+#[test]
+fn test_unseen_and_me() {
+    let adam = DebugObject::adam();
+    let apples = DebugObject::apples();
+    let mut viewer = DebugOutput::new();
+    viewer.can_see = false;
+    viewer.me_id = Some(adam.id());
+    let mut out = JsonOutput::new(vec![], viewer);
+
+    out.out().the(&adam).v(&adam, "see").the(&apples);
+    assert_eq!(
+        events(out)[0].segments,
+        vec![
+            object("You", &adam, NameForm::Pronoun, true),
+            text(" see "),
+            object("something", &apples, NameForm::Unseen, false),
+            text("."),
+        ]
+    );
+}
+
+#[test]
+fn test_styles() {
+    let adam = DebugObject::adam();
+    let mut out = JsonOutput::new(vec![], DebugOutput::new());
+
+    out.out()
+        .style("npc")
+        .the(&adam)
+        .end_style()
+        .s("says")
+        .style("speech")
+        .s("hello");
+    out.out();
+    let events = events(out);
+    assert_eq!(events.len(), 1);
+    let mut adam_segment = object("Adam", &adam, NameForm::ShortName, true);
+    adam_segment.styles = vec![String::from("npc")];
+    let mut hello = text("hello");
+    hello.styles = vec![String::from("speech")];
+    assert_eq!(
+        events[0].segments,
+        vec![adam_segment, text(" says "), hello, text(".")]
+    );
+}
+
+#[test]
+fn test_json_format() {
+    let adam = DebugObject::adam();
+    let mut out = JsonOutput::new(vec![], DebugOutput::new());
+
+    out.out().the(&adam).s("waves");
+    let text = String::from_utf8(out.into_inner()).unwrap();
+    assert_eq!(
+        text,
+        format!(
+            "{{\"text\":\"Adam waves.\",\"segments\":[\
+             {{\"text\":\"Adam\",\"object\":{{\"id\":{},\"form\":\"short_name\",\"seen\":true}}}},\
+             {{\"text\":\" waves.\"}}]}}\n",
+            adam.id()
+        )
+    );
+}
+
+#[test]
+fn test_narration() {
+    let adam = DebugObject::adam();
+    let mut viewer = DebugOutput::new();
+    viewer.me_id = Some(adam.id());
+    let mut out = JsonOutput::new(vec![], viewer).with_narration(Narration::First);
+
+    out.out().the(&adam).v(&adam, "be").s("hungry");
+    assert_eq!(events(out)[0].text, "I am hungry.");
+}
+
+#[test]
+fn test_contractions() {
+    let adam = DebugObject::adam();
+    let mut out = JsonOutput::new(vec![], DebugOutput::new()).with_contractions(true);
+
+    out.out().the(&adam).v_not(&adam, "want").s("it");
+    assert_eq!(events(out)[0].text, "Adam doesn't want it.");
+}
@@ -1,5 +1,3 @@
-use langgen;
-
 mod common;
 
 use crate::common::DebugObject;
@@ -22,3 +20,13 @@ fn long_name() {
     let eva = DebugObject::eva();
     assert_eq!(eva.long_name(), "Eva Adamsfru");
 }
+
+#[test]
+fn object_id() {
+    let adam = DebugObject::adam();
+    let eva = DebugObject::eva();
+    assert_eq!(adam.id(), adam.id());
+    assert_ne!(adam.id(), eva.id());
+    assert!(adam.is_same(&adam));
+    assert!(!adam.is_same(&eva));
+}
@@ -1,5 +1,3 @@
-
-
 mod common;
 
 use crate::common::*;
@@ -238,6 +236,308 @@ fn test_verb_endings() {
     out.can_see = true;
 }
 
+#[test]
+fn test_irregular_verbs() {
+    let mut out = DebugOutput::new();
+    let adam = DebugObject::adam();
+    let apples = DebugObject::apples();
+
+    out.out().the(&adam).v(&adam, "be").s("here");
+    assert_eq!(out.last_text, "Adam is here.");
+
+    out.out().the(&apples).v(&apples, "be").s("here");
+    assert_eq!(out.last_text, "The apples are here.");
+
+    out.out().the(&adam).v(&adam, "can").s("jump");
+    assert_eq!(out.last_text, "Adam can jump.");
+
+    out.out().the(&adam).v_e(&adam, "pick up").the(&apples);
+    assert_eq!(out.last_text, "Adam picks up the apples.");
+
+    out.out()
+        .v(&adam, "do")
+        .the(&adam)
+        .s("want")
+        .the(&apples)
+        .suppress_dot();
+    assert_eq!(out.last_text, "Does Adam want the apples");
+
+    out.out()
+        .v_e(&apples, "be")
+        .the(&apples)
+        .s("red")
+        .suppress_dot();
+    assert_eq!(out.last_text, "Are the apples red");
+
+    out.me = true;
+    out.out().the(&adam).v(&adam, "be").s("here");
+    assert_eq!(out.last_text, "You are here.");
+}
+
+#[test]
+fn test_tenses() {
+    let mut out = DebugOutput::new();
+    let adam = DebugObject::adam();
+    let apples = DebugObject::apples();
+
+    out.out()
+        .tense(verb::Tense::Past)
+        .the(&adam)
+        .v(&adam, "give")
+        .s("you")
+        .the(&apples);
+    assert_eq!(out.last_text, "Adam gave you the apples.");
+
+    out.out()
+        .tense(verb::Tense::Future)
+        .the(&adam)
+        .v(&adam, "pick up")
+        .the(&apples);
+    assert_eq!(out.last_text, "Adam will pick up the apples.");
+
+    out.out()
+        .tense(verb::Tense::PresentProgressive)
+        .the(&adam)
+        .v(&adam, "eat")
+        .the(&apples);
+    assert_eq!(out.last_text, "Adam is eating the apples.");
+
+    out.out()
+        .tense(verb::Tense::PastProgressive)
+        .the(&apples)
+        .is(&apples)
+        .s("red");
+    assert_eq!(out.last_text, "The apples were red.");
+
+    out.tense = verb::Tense::Past;
+    out.out().the(&adam).is(&adam).s("hungry");
+    assert_eq!(out.last_text, "Adam was hungry.");
+
+    out.out().the(&adam).has(&adam).the(&apples);
+    assert_eq!(out.last_text, "Adam had the apples.");
+
+    out.me = true;
+    out.out().the(&adam).is(&adam).s("hungry");
+    assert_eq!(out.last_text, "You were hungry.");
+}
+
+#[test]
+fn test_negations_and_questions() {
+    let mut out = DebugOutput::new();
+    let adam = DebugObject::adam();
+    let apple = DebugObject::apple();
+    let apples = DebugObject::apples();
+
+    out.out().the(&adam).v_not(&adam, "see").the(&apple);
+    assert_eq!(out.last_text, "Adam does not see the apple.");
+
+    out.out()
+        .contractions(true)
+        .the(&adam)
+        .v_not(&adam, "see")
+        .the(&apple);
+    assert_eq!(out.last_text, "Adam doesn't see the apple.");
+
+    out.out().ask(&adam, "want").the(&apple);
+    assert_eq!(out.last_text, "Does Adam want the apple?");
+
+    out.out().ask(&apples, "be").s("red");
+    assert_eq!(out.last_text, "Are the apples red?");
+
+    out.out().ask_not(&adam, "want").the(&apple);
+    assert_eq!(out.last_text, "Does Adam not want the apple?");
+
+    out.contractions = true;
+    out.out().ask_not(&adam, "want").the(&apple);
+    assert_eq!(out.last_text, "Doesn't Adam want the apple?");
+
+    out.tense = verb::Tense::Past;
+    out.out().ask(&adam, "eat").the(&apple);
+    assert_eq!(out.last_text, "Did Adam eat the apple?");
+
+    out.me = true;
+    out.out().the(&adam).v_not(&adam, "have").s("that");
+    assert_eq!(out.last_text, "You didn't have that.");
+}
+
+#[test]
+fn test_narration() {
+    let mut out = DebugOutput::new();
+    let adam = DebugObject::adam();
+    let eva = DebugObject::eva();
+    let apple = DebugObject::apple();
+    out.me_id = Some(adam.id());
+
+    out.narration = Narration::First;
+    out.out()
+        .the(&adam)
+        .v(&adam, "give")
+        .my(&adam, &apple)
+        .s("to")
+        .the(&eva);
+    assert_eq!(out.last_text, "I give my apple to Eva.");
+
+    out.out()
+        .the(&eva)
+        .v(&eva, "give")
+        .the(&apple)
+        .s("to")
+        .the(&adam);
+    assert_eq!(out.last_text, "Eva gives the apple to me.");
+
+    out.out().the(&eva).s("looks at").the(&adam);
+    assert_eq!(out.last_text, "Eva looks at me.");
+
+    out.out()
+        .the(&eva)
+        .s("smiles, and")
+        .the(&adam)
+        .s("look at")
+        .the(&eva);
+    assert_eq!(out.last_text, "Eva smiles, and I look at Eva.");
+
+    out.out().the(&eva).s("sees").the_list(&[&apple, &adam]);
+    assert_eq!(out.last_text, "Eva sees the apple and me.");
+
+    out.out().ask(&adam, "want").the(&apple);
+    assert_eq!(out.last_text, "Do I want the apple?");
+
+    out.out().the(&adam).is(&adam).s("hungry");
+    assert_eq!(out.last_text, "I am hungry.");
+
+    out.out().he(&adam).v(&adam, "hurt").himself(&adam);
+    assert_eq!(out.last_text, "I hurt myself.");
+
+    out.out().thes(&adam).s("apple").is(&apple).s("red");
+    assert_eq!(out.last_text, "My apple is red.");
+
+    out.out().the(&eva).v(&eva, "take").hiss(&adam);
+    assert_eq!(out.last_text, "Eva takes mine.");
+
+    let both = Compound::new(&[&adam, &eva]).unwrap();
+    out.out().the(&both).v(&both, "be").s("here");
+    assert_eq!(out.last_text, "Eva and I are here.");
+
+    let orc = DebugObject::new("orc, green orc", Gender::Male, false);
+    let orcs = ObjectGroup::counted(3, &orc).unwrap();
+    out.out()
+        .the(&eva)
+        .v(&eva, "give")
+        .the(&apple)
+        .s("to")
+        .the(&orcs)
+        .s("and")
+        .the(&adam);
+    assert_eq!(
+        out.last_text,
+        "Eva gives the apple to the three orcs and me."
+    );
+
+    out.narration = Narration::Third;
+    out.out()
+        .the(&adam)
+        .v(&adam, "give")
+        .my(&adam, &apple)
+        .s("to")
+        .the(&eva);
+    assert_eq!(out.last_text, "Adam gives his apple to Eva.");
+
+    out.out().thes(&adam).s("apple").is(&apple).s("red");
+    assert_eq!(out.last_text, "Adam's apple is red.");
+
+    out.out()
+        .narration(Narration::Second)
+        .the(&adam)
+        .is(&adam)
+        .s("hungry");
+    assert_eq!(out.last_text, "You are hungry.");
+}
+
+#[test]
+fn test_contractions() {
+    let mut out = DebugOutput::new();
+    let adam = DebugObject::adam();
+    let eva = DebugObject::eva();
+    let apples = DebugObject::apples();
+
+    out.out().he_s(&adam).s("hungry");
+    assert_eq!(out.last_text, "He is hungry.");
+
+    out.contractions = true;
+    out.out().he_s(&adam).s("hungry");
+    assert_eq!(out.last_text, "He's hungry.");
+
+    out.out().he_s(&apples).s("red");
+    assert_eq!(out.last_text, "They're red.");
+
+    out.out().he_has(&apples).s("fallen");
+    assert_eq!(out.last_text, "They've fallen.");
+
+    out.out().he(&adam).has(&adam).the(&apples);
+    assert_eq!(out.last_text, "He has the apples.");
+
+    out.out().he(&eva).v_not(&eva, "can").s("swim");
+    assert_eq!(out.last_text, "She can't swim.");
+
+    out.out()
+        .tense(verb::Tense::Future)
+        .he(&eva)
+        .v(&eva, "swim");
+    assert_eq!(out.last_text, "She'll swim.");
+
+    out.out().the(&eva).is(&eva).s("here");
+    assert_eq!(out.last_text, "Eva is here.");
+
+    out.me_id = Some(adam.id());
+    out.out().the(&adam).is(&adam).s("hungry");
+    assert_eq!(out.last_text, "You're hungry.");
+
+    out.narration = Narration::First;
+    out.out().the(&adam).v_not(&adam, "be").s("hungry");
+    assert_eq!(out.last_text, "I'm not hungry.");
+
+    out.out()
+        .contractions(false)
+        .the(&adam)
+        .is(&adam)
+        .s("hungry");
+    assert_eq!(out.last_text, "I am hungry.");
+}
+
+#[test]
+fn test_styles() {
+    let mut out = DebugOutput::new();
+    let adam = DebugObject::adam();
+    let apple = DebugObject::apple();
+
+    out.out()
+        .style("npc")
+        .the(&adam)
+        .end_style()
+        .v(&adam, "take")
+        .style("item")
+        .the(&apple)
+        .end_style();
+    assert_eq!(out.last_text, "<npc>Adam<> takes <item>the apple<>.");
+
+    out.out()
+        .style("bold")
+        .s("hello")
+        .end_style()
+        .s("world")
+        .style("bold")
+        .end_style();
+    assert_eq!(out.last_text, "<bold>Hello<> world<bold><>.");
+
+    out.out()
+        .the(&adam)
+        .s("says")
+        .style("speech")
+        .s("hi!")
+        .end_style();
+    assert_eq!(out.last_text, "Adam says <speech>hi!<>");
+}
+
 #[test]
 fn test_out_for_me() {
     let mut out = DebugOutput::new();
@@ -256,6 +556,22 @@ fn test_out_for_me() {
     assert_eq!(out.text, "");
 }
 
+#[test]
+fn test_out_for_me_id() {
+    let mut out = DebugOutput::new();
+
+    let adam = DebugObject::adam();
+    let eva = DebugObject::eva();
+
+    out.me_id = Some(adam.id());
+
+    out.out().the(&adam).v_e(&adam, "kiss").the(&eva);
+    assert_eq!(out.last_text, "You kiss Eva.");
+
+    out.out().the(&eva).v_e(&eva, "kiss").the(&adam);
+    assert_eq!(out.last_text, "Eva kisses you.");
+}
+
 #[test]
 fn test_my() {
     let mut out = DebugOutput::new();
@@ -450,6 +766,277 @@ fn test_thes_() {
     assert_eq!(out.last_text, "Your.");
 }
 
+#[test]
+fn test_unseen_name() {
+    let mut out = DebugOutput::new();
+    let nf = named::Factory::from_reader(&mut std::io::Cursor::new("")).unwrap();
+    let mut def = named::NamedDef::new("ghost", Gender::Male, false);
+    def.unseen = Some(String::from("a cold breeze"));
+    let ghost = DebugObject::from_named(Box::new(nf.create_from_def(&def)));
+    let apple = DebugObject::apple();
+
+    out.out().the(&ghost).v_e(&ghost, "take").the(&apple);
+    assert_eq!(out.last_text, "The ghost takes the apple.");
+
+    out.can_see = false;
+    out.out().the(&ghost).v_e(&ghost, "take").the(&apple);
+    assert_eq!(out.last_text, "A cold breeze takes something.");
+
+    out.out().thes(&ghost);
+    assert_eq!(out.last_text, "A cold breeze's.");
+}
+
+#[test]
+fn test_lists() {
+    let mut out = DebugOutput::new();
+    let adam = DebugObject::adam();
+    let eva = DebugObject::eva();
+    let apple = DebugObject::apple();
+    let apple2 = DebugObject::apple();
+    let apple3 = DebugObject::apple();
+    let knife = DebugObject::knife();
+    let dust = DebugObject::dust();
+
+    out.out().a_list(&[]);
+    assert_eq!(out.last_text, "Nothing.");
+
+    out.out().a_list(&[&apple]);
+    assert_eq!(out.last_text, "An apple.");
+
+    out.out().a_list(&[&apple, &knife]);
+    assert_eq!(out.last_text, "An apple and a knife.");
+
+    out.out().oxford_comma().a_list(&[&apple, &knife]);
+    assert_eq!(out.last_text, "An apple and a knife.");
+
+    out.out().a_list(&[&apple, &knife, &apple2, &dust, &apple3]);
+    assert_eq!(out.last_text, "Three apples, a knife and some dust.");
+
+    out.out()
+        .oxford_comma()
+        .a_list(&[&apple, &knife, &apple2, &dust, &apple3]);
+    assert_eq!(out.last_text, "Three apples, a knife, and some dust.");
+
+    out.out()
+        .s("you see")
+        .the_list(&[&adam, &apple, &eva, &apple2]);
+    assert_eq!(out.last_text, "You see Adam, the two apples and Eva.");
+
+    let all = Compound::new(&[&eva, &adam, &knife]).unwrap();
+    out.out()
+        .the_list(&[&eva, &adam, &knife])
+        .is(&all)
+        .s("here");
+    assert_eq!(out.last_text, "Eva, Adam and the knife are here.");
+
+    out.me_id = Some(adam.id());
+    out.out().the_list(&[&eva, &adam, &knife]);
+    assert_eq!(out.last_text, "Eva, you and the knife.");
+    out.me_id = None;
+
+    let red_apple = DebugObject::new("apple, red apple", Gender::Neuter, true);
+    out.out().a_list(&[&apple, &red_apple, &apple2]);
+    assert_eq!(out.last_text, "Two apples and an apple.");
+    out.out()
+        .a_(&ObjectGroup::new(&[&apple, &red_apple, &apple2]).unwrap());
+    assert_eq!(out.last_text, "Two green apples and a red apple.");
+
+    out.can_see = false;
+    out.out().a_list(&[&apple]);
+    assert_eq!(out.last_text, "Something.");
+
+    out.out().a_list(&[&apple, &knife, &adam]);
+    assert_eq!(out.last_text, "Some things.");
+}
+
+#[test]
+fn test_pronouns() {
+    let mut out = DebugOutput::new();
+    let adam = DebugObject::adam();
+    let eva = DebugObject::eva();
+    let apple = DebugObject::apple();
+    let apples = DebugObject::apples();
+    let dust = DebugObject::dust();
+
+    for (who, res) in &[
+        (&adam, "He his his him himself."),
+        (&eva, "She her hers her herself."),
+        (&apple, "It its its it itself."),
+        (&apples, "They their theirs them themselves."),
+        (&dust, "It its its it itself."),
+    ] {
+        out.out()
+            .he(*who)
+            .his(*who)
+            .hiss(*who)
+            .him(*who)
+            .himself(*who);
+        assert_eq!(out.last_text, *res);
+    }
+
+    out.me = true;
+    out.out()
+        .he(&adam)
+        .his(&adam)
+        .hiss(&adam)
+        .him(&adam)
+        .himself(&adam);
+    assert_eq!(out.last_text, "You your yours you yourself.");
+    out.me = false;
+
+    out.can_see = false;
+    out.out().he(&eva).his(&eva).him(&eva);
+    assert_eq!(out.last_text, "He his him.");
+    out.out().he(&apples).his(&apples).him(&apples);
+    assert_eq!(out.last_text, "It its it.");
+}
+
+#[test]
+fn test_the_or_himself() {
+    let mut out = DebugOutput::new();
+    let adam = DebugObject::adam();
+    let eva = DebugObject::eva();
+
+    out.out()
+        .the(&adam)
+        .v_e(&adam, "hit")
+        .the_or_himself(&adam, &eva);
+    assert_eq!(out.last_text, "Adam hits Eva.");
+
+    out.out()
+        .the(&adam)
+        .v_e(&adam, "hit")
+        .the_or_himself(&adam, &adam);
+    assert_eq!(out.last_text, "Adam hits himself.");
+
+    out.me_id = Some(adam.id());
+    out.out()
+        .the(&adam)
+        .v_e(&adam, "hit")
+        .the_or_himself(&adam, &adam);
+    assert_eq!(out.last_text, "You hit yourself.");
+}
+
+#[test]
+fn test_compound() {
+    let mut out = DebugOutput::new();
+    let adam = DebugObject::adam();
+    let eva = DebugObject::eva();
+    let apple = DebugObject::apple();
+    let knife = DebugObject::knife();
+
+    let adam_and_eva = Compound::new(&[&adam, &eva]).unwrap();
+    out.out()
+        .the(&adam_and_eva)
+        .v_e(&adam_and_eva, "give")
+        .the(&knife)
+        .a(&apple);
+    assert_eq!(out.last_text, "Adam and Eva give the knife an apple.");
+
+    out.out()
+        .the(&adam_and_eva)
+        .is(&adam_and_eva)
+        .s("hungry and")
+        .he(&adam_and_eva)
+        .has(&adam_and_eva)
+        .his(&adam_and_eva)
+        .s("knife");
+    assert_eq!(
+        out.last_text,
+        "Adam and Eva are hungry and they have their knife."
+    );
+
+    out.out()
+        .the_(&adam_and_eva)
+        .v_e(&adam_and_eva, "wash")
+        .himself(&adam_and_eva);
+    assert_eq!(
+        out.last_text,
+        "Adam Evasman and Eva Adamsfru wash themselves."
+    );
+
+    out.me_id = Some(adam.id());
+    out.out()
+        .the(&adam_and_eva)
+        .v_e(&adam_and_eva, "fall")
+        .s("and hurt")
+        .himself(&adam_and_eva);
+    assert_eq!(out.last_text, "You and Eva fall and hurt yourselves.");
+    out.me_id = None;
+
+    assert!(Compound::new(&[]).is_none());
+
+    let just_eva = Compound::new(&[&eva]).unwrap();
+    out.out().the(&just_eva).v_e(&just_eva, "fall");
+    assert_eq!(out.last_text, "Eva falls.");
+
+    let things = Compound::new(&[&apple, &knife]).unwrap();
+    out.out().a(&things).v_e(&things, "fall");
+    assert_eq!(out.last_text, "An apple and a knife fall.");
+
+    out.can_see = false;
+    out.out().a(&things).v_e(&things, "fall");
+    assert_eq!(out.last_text, "Some things fall.");
+}
+
+#[test]
+fn test_object_group() {
+    let mut out = DebugOutput::new();
+    let adam = DebugObject::adam();
+    let orc = DebugObject::new("orc, green orc", Gender::Male, false);
+    let wolf = DebugObject::new("wolf, grey wolf", Gender::Neuter, false);
+
+    assert!(ObjectGroup::new(&[]).is_none());
+    assert!(ObjectGroup::counted(0, &orc).is_none());
+    assert!(ObjectGroup::plural(0, &orc).is_none());
+
+    let orcs = ObjectGroup::counted(3, &orc).unwrap();
+    assert_eq!(orcs.len(), 3);
+    out.out().a(&orcs).v_e(&orcs, "attack").the(&adam);
+    assert_eq!(out.last_text, "Three orcs attack Adam.");
+
+    out.out()
+        .the_(&orcs)
+        .v_e(&orcs, "raise")
+        .his(&orcs)
+        .s("axes");
+    assert_eq!(out.last_text, "The three green orcs raise their axes.");
+
+    let goblins = ObjectGroup::plural(4, &orc).unwrap();
+    out.out().the(&goblins).is(&goblins).s("angry");
+    assert_eq!(out.last_text, "The orcs are angry.");
+    out.out().a_(&goblins).v_e(&goblins, "arrive");
+    assert_eq!(out.last_text, "Some green orcs arrive.");
+
+    let nf = named::Factory::from_reader(&mut std::io::Cursor::new("")).unwrap();
+    let pack = ObjectGroup::counted(5, &wolf)
+        .unwrap()
+        .with_name(nf.create("pack of wolves", Gender::Neuter, false).unwrap());
+    out.out().a(&pack).v_e(&pack, "howl").s("at").him(&pack);
+    assert_eq!(out.last_text, "A pack of wolves howl at them.");
+
+    let mixed = ObjectGroup::new(&[&adam, &orc, &wolf, &orc]).unwrap();
+    out.out().the(&mixed).v_e(&mixed, "fight");
+    assert_eq!(out.last_text, "Adam, the two orcs and the wolf fight.");
+
+    out.me_id = Some(adam.id());
+    out.out().the(&mixed).v_e(&mixed, "fight");
+    assert_eq!(out.last_text, "You, the two orcs and the wolf fight.");
+    out.me_id = None;
+
+    // The viewer only knows the members, not the group.
+    out.seen_ids = Some(vec![orc.id()]);
+    out.out().thes(&orcs).s("axes").v_e(&orcs, "shine");
+    assert_eq!(out.last_text, "The three orcs' axes shine.");
+    out.out().thes_(&goblins).s("axes").v_e(&orcs, "shine");
+    assert_eq!(out.last_text, "The green orcs' axes shine.");
+    out.seen_ids = None;
+
+    out.can_see = false;
+    out.out().a(&pack).v_e(&pack, "howl");
+    assert_eq!(out.last_text, "Some things howl.");
+}
+
 #[test]
 fn test_suppress_dot() {
     let mut out = DebugOutput::new();
@@ -457,3 +1044,15 @@ fn test_suppress_dot() {
     out.out().s("hello").suppress_dot();
     assert_eq!(out.last_text, "Hello");
 }
+
+#[test]
+fn test_unclosed_styles() {
+    let mut out = DebugOutput::new();
+    let adam = DebugObject::adam();
+
+    out.out().style("npc").the(&adam).style("bold").s("waves");
+    assert_eq!(out.last_text, "<npc>Adam <bold>waves<><>.");
+
+    out.out().the(&adam).end_style().s("waves");
+    assert_eq!(out.last_text, "Adam waves.");
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use crate::common::*;
+use langgen::broadcast::broadcast;
+use langgen::sense::{Sense, SenseChain};
+use langgen::templates::{Template, TemplateText};
+use langgen::*;
+
+fn chain() -> SenseChain {
+    SenseChain::parse(
+        "me",
+        "see: \\The(me) give\\s(me) \\a(obj) to \\the(env).\n\
+         hear: You hear someone moving nearby.\n\
+         feel: You feel a draft.",
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_fallbacks() {
+    let adam = DebugObject::adam();
+    let eva = DebugObject::eva();
+    let apple = DebugObject::apple();
+    let ctx = DebugContext::new(&adam).obj(&apple).env(&eva);
+
+    let mut actor = DebugOutput::new();
+    actor.me_id = Some(adam.id());
+    actor.can_see = false;
+    let seeing = DebugOutput::new();
+    let mut blind = DebugOutput::new();
+    blind.can_see = false;
+    let mut deaf_blind = DebugOutput::new();
+    deaf_blind.can_see = false;
+    deaf_blind.cannot = vec!["hear"];
+    let mut numb = DebugOutput::new();
+    numb.can_see = false;
+    numb.cannot = vec!["hear", "feel"];
+    numb.last_text = String::from("unchanged");
+    let mut outs = [actor, seeing, blind, deaf_blind, numb];
+
+    broadcast(
+        &chain(),
+        &ctx,
+        outs.iter_mut().map(|o| o as &mut dyn Output),
+    );
+    assert_eq!(outs[0].last_text, "You give something to someone.");
+    assert_eq!(outs[1].last_text, "Adam gives an apple to Eva.");
+    assert_eq!(outs[2].last_text, "You hear someone moving nearby.");
+    assert_eq!(outs[3].last_text, "You feel a draft.");
+    assert_eq!(outs[4].last_text, "unchanged");
+}
+
+#[test]
+fn test_builder() {
+    let adam = DebugObject::adam();
+    let ctx = DebugContext::new(&adam);
+    let chain = SenseChain::new("me")
+        .or(
+            Sense::Hear,
+            TemplateText::parse("\\The(me) sing\\s(me).").unwrap(),
+        )
+        .or(
+            Sense::Smell,
+            TemplateText::parse("You smell roses.").unwrap(),
+        );
+    let mut deaf = DebugOutput::new();
+    deaf.cannot = vec!["hear"];
+    let mut outs = [DebugOutput::new(), deaf];
+
+    broadcast(&chain, &ctx, outs.iter_mut().map(|o| o as &mut dyn Output));
+    assert_eq!(outs[0].last_text, "Adam sings.");
+    assert_eq!(outs[1].last_text, "You smell roses.");
+
+    // num is not an Object, so the first template is used.
+    let chain = SenseChain::parse("num", "hear: You hear a bell.\nfeel: It shakes.").unwrap();
+    chain.render(&ctx, &mut outs[1]);
+    assert_eq!(outs[1].last_text, "You hear a bell.");
+}
+
+#[test]
+fn test_parse_errors() {
+    let err = |text| SenseChain::parse("me", text).err().unwrap();
+    assert_eq!(err("taste: Yummy."), "Unknown sense \"taste\"");
+    assert_eq!(err("You hear."), "Missing sense in \"You hear.\"");
+    assert_eq!(Sense::from_verb("smell"), Some(Sense::Smell));
+    assert_eq!(Sense::Feel.verb(), "feel");
+}
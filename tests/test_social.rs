@@ -0,0 +1,133 @@
+mod common;
+
+use crate::common::*;
+use langgen::social::{SocialDef, Socials};
+use langgen::*;
+
+fn viewer(me: &DebugObject) -> DebugOutput {
+    let mut out = DebugOutput::new();
+    out.me_id = Some(me.id());
+    out
+}
+
+#[test]
+fn test_standard_socials() {
+    let socials = Socials::standard();
+    assert_eq!(socials.names(), vec!["bow", "hug", "smile", "wave"]);
+
+    let adam = DebugObject::adam();
+    let eva = DebugObject::eva();
+    let me = DebugObject::me();
+    let mut outs = [viewer(&adam), viewer(&eva), viewer(&me)];
+    let hug = socials.get("hug").unwrap();
+
+    hug.act(
+        &adam,
+        Some(&eva),
+        outs.iter_mut().map(|o| o as &mut dyn Output),
+    )
+    .unwrap();
+    assert_eq!(outs[0].last_text, "You hug Eva.");
+    assert_eq!(outs[1].last_text, "Adam hugs you.");
+    assert_eq!(outs[2].last_text, "Adam hugs Eva.");
+
+    hug.act(&adam, None, outs.iter_mut().map(|o| o as &mut dyn Output))
+        .unwrap();
+    assert_eq!(outs[0].last_text, "You hug the air.");
+    assert_eq!(outs[1].last_text, "Adam hugs the air.");
+
+    hug.act(
+        &adam,
+        Some(&adam),
+        outs.iter_mut().map(|o| o as &mut dyn Output),
+    )
+    .unwrap();
+    assert_eq!(outs[0].last_text, "You hug yourself.");
+    assert_eq!(outs[1].last_text, "Adam hugs himself.");
+}
+
+#[test]
+fn test_unseen_target() {
+    let socials = Socials::standard();
+    let adam = DebugObject::adam();
+    let eva = DebugObject::eva();
+    let mut blind = viewer(&DebugObject::me());
+    blind.can_see = false;
+    let mut target = viewer(&eva);
+    target.can_see = false;
+    let mut outs = [viewer(&adam), target, blind];
+
+    socials
+        .get("smile")
+        .unwrap()
+        .act(
+            &adam,
+            Some(&eva),
+            outs.iter_mut().map(|o| o as &mut dyn Output),
+        )
+        .unwrap();
+    assert_eq!(outs[0].last_text, "You smile at Eva.");
+    assert_eq!(outs[1].last_text, "Someone smiles at you.");
+    assert_eq!(
+        outs[2].last_text,
+        "Someone smiles at someone you can't see."
+    );
+}
+
+#[test]
+fn test_from_reader() {
+    let text = "# Comment\n\
+                [nod]\n\
+                none: \\The(me) nod\\s(me).\n\
+                \n\
+                [poke]\n\
+                target: \\The(me) poke\\s(me) \\the(obj).\n";
+    let socials = Socials::from_reader(&mut text.as_bytes()).unwrap();
+    assert_eq!(socials.names(), vec!["nod", "poke"]);
+
+    let adam = DebugObject::adam();
+    let eva = DebugObject::eva();
+    let mut out = viewer(&eva);
+    let outs = || std::iter::empty::<&mut dyn Output>();
+    let nod = socials.get("nod").unwrap();
+    assert!(nod.has_none() && !nod.has_target());
+    assert_eq!(
+        nod.act(&adam, Some(&eva), outs()),
+        Err(String::from("nod can't have a target"))
+    );
+    assert_eq!(
+        socials.get("poke").unwrap().act(&adam, None, outs()),
+        Err(String::from("poke needs a target"))
+    );
+    socials
+        .get("poke")
+        .unwrap()
+        .act(&adam, Some(&eva), vec![&mut out as &mut dyn Output])
+        .unwrap();
+    assert_eq!(out.last_text, "Adam pokes you.");
+}
+
+#[test]
+fn test_bad_socials() {
+    let bad = |text: &str| Socials::from_reader(&mut text.as_bytes()).err().unwrap();
+    assert_eq!(bad("none: Hi."), "No [social] before line 1");
+    assert_eq!(
+        bad("[nod]\nboth: Hi."),
+        "Unknown variant \"both\" on line 2"
+    );
+    assert_eq!(bad("[nod]\n"), "The social \"nod\" has no templates");
+    assert!(bad("[nod]\nnone Hi").contains("wrong format on line 2"));
+    assert!(bad("[nod]\nnone: \\bad(me)").starts_with("The social \"nod\": "));
+    let mut not_utf8: &[u8] = b"[nod]\nnone: \xff\n";
+    let err = Socials::from_reader(&mut not_utf8).err().unwrap();
+    assert!(err.starts_with("Could not read line 2: "));
+
+    let mut socials = Socials::new();
+    let def = SocialDef {
+        name: String::from("nod"),
+        none: Some(String::from("\\The(me) nod\\s(me).")),
+        ..SocialDef::default()
+    };
+    socials.add(&def).unwrap();
+    assert_eq!(socials.names(), vec!["nod"]);
+}
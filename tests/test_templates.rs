@@ -0,0 +1,373 @@
+mod common;
+
+use crate::common::*;
+use langgen::templates::{Context, ObjectRef, Template, TemplateText};
+use langgen::*;
+
+fn render(text: &str, ctx: &DebugContext, out: &mut DebugOutput) -> String {
+    TemplateText::parse(text).unwrap().render(ctx, out);
+    out.last_text.clone()
+}
+
+#[test]
+fn test_render() {
+    let mut out = DebugOutput::new();
+    let adam = DebugObject::adam();
+    let apple = DebugObject::apple();
+    let eva = DebugObject::eva();
+    let ctx = DebugContext::new(&adam).obj(&apple).env(&eva);
+    let text = "\\The(me) give\\s(me) \\a(obj) to \\the_(env).";
+
+    assert_eq!(
+        render(text, &ctx, &mut out),
+        "Adam gives an apple to Eva Adamsfru."
+    );
+
+    out.me_id = Some(adam.id());
+    assert_eq!(
+        render(text, &ctx, &mut out),
+        "You give an apple to Eva Adamsfru."
+    );
+    out.me_id = None;
+
+    out.can_see = false;
+    assert_eq!(
+        render(text, &ctx, &mut out),
+        "Someone gives something to someone."
+    );
+}
+
+#[test]
+fn test_render_case_and_spacing() {
+    let mut out = DebugOutput::new();
+    let adam = DebugObject::adam();
+    let apple = DebugObject::apple();
+    let ctx = DebugContext::new(&adam).obj(&apple);
+
+    assert_eq!(
+        render("\\the(obj) falls", &ctx, &mut out),
+        "the apple falls."
+    );
+    assert_eq!(
+        render("look at \\the(obj)!", &ctx, &mut out),
+        "Look at the apple!"
+    );
+    assert_eq!(
+        render("\\The(me) say\\s(me) ''\\the(obj)''", &ctx, &mut out),
+        "Adam says ''the apple''."
+    );
+    assert_eq!(render("a \\\\ b", &ctx, &mut out), "A \\ b.");
+}
+
+#[test]
+fn test_render_verbs() {
+    let mut out = DebugOutput::new();
+    let adam = DebugObject::adam();
+    let apples = DebugObject::apples();
+    let ctx = DebugContext::new(&adam).obj(&apples);
+
+    assert_eq!(
+        render("\\The(me) \\v(me, be) hungry.", &ctx, &mut out),
+        "Adam is hungry."
+    );
+    assert_eq!(
+        render("\\The(obj) \\v(obj, be) red.", &ctx, &mut out),
+        "The apples are red."
+    );
+    assert_eq!(
+        render("\\V(me, be) \\the(me) hungry?", &ctx, &mut out),
+        "Is Adam hungry?"
+    );
+    assert_eq!(
+        render("\\The(me) \\v(me, go) home.", &ctx, &mut out),
+        "Adam goes home."
+    );
+
+    out.me_id = Some(adam.id());
+    assert_eq!(
+        render(
+            "\\The(me) \\v(me, be) hungry and \\v(me, have) \\the(obj).",
+            &ctx,
+            &mut out
+        ),
+        "You are hungry and have the apples."
+    );
+    assert_eq!(
+        render("\\V(me, do) \\he(me) want \\him(obj)?", &ctx, &mut out),
+        "Do you want them?"
+    );
+}
+
+#[test]
+fn test_render_numbers() {
+    let mut out = DebugOutput::new();
+    let adam = DebugObject::adam();
+    let apple = DebugObject::apple();
+    let ctx = DebugContext::new(&adam).obj(&apple).num(3);
+
+    assert_eq!(
+        render("\\The(me) \\v(me, have) \\num(num) coins.", &ctx, &mut out),
+        "Adam has 3 coins."
+    );
+    assert_eq!(
+        render("\\The(me) \\v(me, have) \\snum(num) coins.", &ctx, &mut out),
+        "Adam has three coins."
+    );
+    assert_eq!(
+        render("\\The(me) \\v(me, eat) \\the(objs).", &ctx, &mut out),
+        "Adam eats the three apples."
+    );
+    assert_eq!(
+        render("\\The(objs) \\v(objs, be) gone.", &ctx, &mut out),
+        "The three apples are gone."
+    );
+
+    for num in [0, -2] {
+        let ctx = DebugContext::new(&adam).obj(&apple).num(num);
+        out.last_text.clear();
+        assert_eq!(
+            render("\\The(objs) \\v(objs, be) gone.", &ctx, &mut out),
+            ""
+        );
+    }
+}
+
+/// A Context that panics on names it doesn't know.
+struct StrictContext<'a>(&'a DebugObject);
+
+impl<'a> Context for StrictContext<'a> {
+    fn get_me(&self) -> ObjectRef<'_> {
+        ObjectRef::Object(self.0)
+    }
+
+    fn get_obj(&self) -> ObjectRef<'_> {
+        ObjectRef::Object(self.0)
+    }
+
+    fn get_env(&self) -> ObjectRef<'_> {
+        ObjectRef::Object(self.0)
+    }
+
+    fn get(&self, who: &str) -> ObjectRef<'_> {
+        panic!("Unknown name {:?}", who)
+    }
+}
+
+#[test]
+fn test_render_looks_up_only_objects() {
+    let mut out = DebugOutput::new();
+    let apple = DebugObject::apple();
+    let text = "\\The(me) \\v(me, take) \\my(me, obj) and \\ing(run) \\style(bold)off\\style().";
+    TemplateText::parse(text)
+        .unwrap()
+        .render(&StrictContext(&apple), &mut out);
+    assert_eq!(
+        out.last_text,
+        "The apple takes its apple and running <bold>off<>."
+    );
+}
+
+#[test]
+fn test_render_tenses() {
+    let mut out = DebugOutput::new();
+    let adam = DebugObject::adam();
+    let apple = DebugObject::apple();
+    let eva = DebugObject::eva();
+    let ctx = DebugContext::new(&adam).obj(&apple).env(&eva);
+    let text = "\\The(me) give\\s(me) \\a(obj) to \\the_(env).";
+
+    for (tense, expected) in &[
+        (verb::Tense::Present, "Adam gives an apple to Eva Adamsfru."),
+        (verb::Tense::Past, "Adam gave an apple to Eva Adamsfru."),
+        (
+            verb::Tense::Future,
+            "Adam will give an apple to Eva Adamsfru.",
+        ),
+        (
+            verb::Tense::PastProgressive,
+            "Adam was giving an apple to Eva Adamsfru.",
+        ),
+    ] {
+        out.tense = *tense;
+        assert_eq!(render(text, &ctx, &mut out), *expected);
+    }
+
+    out.me_id = Some(adam.id());
+    out.tense = verb::Tense::Past;
+    assert_eq!(
+        render("\\The(me) \\is(me) hungry.", &ctx, &mut out),
+        "You were hungry."
+    );
+}
+
+#[test]
+fn test_render_negations_and_questions() {
+    let mut out = DebugOutput::new();
+    let adam = DebugObject::adam();
+    let apple = DebugObject::apple();
+    let ctx = DebugContext::new(&adam).obj(&apple);
+
+    assert_eq!(
+        render("\\The(me) \\v_not(me, see) \\the(obj).", &ctx, &mut out),
+        "Adam does not see the apple."
+    );
+    assert_eq!(
+        render("\\Ask(me, want) \\the(obj)?", &ctx, &mut out),
+        "Does Adam want the apple?"
+    );
+    assert_eq!(
+        render("\\Ask(me, want) \\the(obj)", &ctx, &mut out),
+        "Does Adam want the apple?"
+    );
+
+    out.me_id = Some(adam.id());
+    out.contractions = true;
+    assert_eq!(
+        render("\\The(me) \\v_not(me, have) that.", &ctx, &mut out),
+        "You don't have that."
+    );
+    assert_eq!(
+        render("\\Ask_not(me, be) hungry?", &ctx, &mut out),
+        "Aren't you hungry?"
+    );
+}
+
+#[test]
+fn test_render_participles() {
+    let mut out = DebugOutput::new();
+    let adam = DebugObject::adam();
+    let apple = DebugObject::apple();
+    let ctx = DebugContext::new(&adam).obj(&apple);
+
+    assert_eq!(
+        render("\\The(me) \\is(me) \\ing(run).", &ctx, &mut out),
+        "Adam is running."
+    );
+    assert_eq!(
+        render("\\The(me) \\v(me, stop) \\ing(give).", &ctx, &mut out),
+        "Adam stops giving."
+    );
+    assert_eq!(
+        render("\\The(obj) \\is(obj) \\ed(drop).", &ctx, &mut out),
+        "The apple is dropped."
+    );
+    assert_eq!(
+        render(
+            "\\The(obj) \\is(obj) \\ed(give) to \\the(me).",
+            &ctx,
+            &mut out
+        ),
+        "The apple is given to Adam."
+    );
+    assert_eq!(
+        render("\\Ing(lie) down is nice.", &ctx, &mut out),
+        "Lying down is nice."
+    );
+}
+
+#[test]
+fn test_render_narration() {
+    let mut out = DebugOutput::new();
+    let adam = DebugObject::adam();
+    let apple = DebugObject::apple();
+    let eva = DebugObject::eva();
+    let ctx = DebugContext::new(&adam).obj(&apple).env(&eva);
+    let text = "\\The(me) give\\s(me) \\a(obj) to \\the_(env).";
+    out.me_id = Some(adam.id());
+
+    out.narration = Narration::First;
+    assert_eq!(
+        render(text, &ctx, &mut out),
+        "I give an apple to Eva Adamsfru."
+    );
+    let ctx2 = DebugContext::new(&eva).obj(&apple).env(&adam);
+    assert_eq!(render(text, &ctx2, &mut out), "Eva gives an apple to me.");
+    assert_eq!(
+        render(
+            "\\The(me) smile\\s(me) at \\the(env); \\the(env) \\v(env, wave).",
+            &ctx2,
+            &mut out
+        ),
+        "Eva smiles at me; I wave."
+    );
+
+    out.narration = Narration::Third;
+    assert_eq!(
+        render(text, &ctx, &mut out),
+        "Adam gives an apple to Eva Adamsfru."
+    );
+}
+
+#[test]
+fn test_render_contractions() {
+    let mut out = DebugOutput::new();
+    let adam = DebugObject::adam();
+    let ctx = DebugContext::new(&adam);
+    let text = "\\He_s(me) hungry and \\he(me) \\v_not(me, have) any food.";
+
+    assert_eq!(
+        render(text, &ctx, &mut out),
+        "He is hungry and he does not have any food."
+    );
+
+    out.contractions = true;
+    assert_eq!(
+        render(text, &ctx, &mut out),
+        "He's hungry and he doesn't have any food."
+    );
+}
+
+#[test]
+fn test_render_styles() {
+    let mut out = DebugOutput::new();
+    let adam = DebugObject::adam();
+    let apple = DebugObject::apple();
+    let ctx = DebugContext::new(&adam).obj(&apple);
+
+    assert_eq!(
+        render(
+            "\\style(npc)\\The(me)\\style() take\\s(me) \\style(item)\\the(obj)\\style().",
+            &ctx,
+            &mut out
+        ),
+        "<npc>Adam<> takes <item>the apple<>."
+    );
+    assert_eq!(
+        render(
+            "\\The(me) say\\s(me) \\style(speech)hello \\style(bold)there",
+            &ctx,
+            &mut out
+        ),
+        "Adam says <speech>hello <bold>there<><>."
+    );
+
+    let ending = "\\The(me) \\style(bold)give\\style()\\s(me) \\the(obj) away.";
+    let verb = "\\The(me) \\style(bold)\\v(me, give)\\style() \\the(obj) away.";
+    for text in [ending, verb] {
+        out.tense = verb::Tense::Present;
+        assert_eq!(
+            render(text, &ctx, &mut out),
+            "Adam <bold>gives<> the apple away."
+        );
+        out.tense = verb::Tense::Past;
+        assert_eq!(
+            render(text, &ctx, &mut out),
+            "Adam <bold>gave<> the apple away."
+        );
+    }
+    let after_style = "\\style(npc)\\The(me)\\style() \\v(me, give) \\the(obj) away.";
+    assert_eq!(
+        render(after_style, &ctx, &mut out),
+        "<npc>Adam<> gave the apple away."
+    );
+    out.tense = verb::Tense::Present;
+    assert_eq!(
+        render(after_style, &ctx, &mut out),
+        "<npc>Adam<> gives the apple away."
+    );
+    out.me_id = Some(adam.id());
+    assert_eq!(
+        render(ending, &ctx, &mut out),
+        "You <bold>give<> the apple away."
+    );
+}
@@ -0,0 +1,131 @@
+mod common;
+
+use crate::common::*;
+use langgen::wrap::WrapOutput;
+use langgen::*;
+
+#[test]
+fn test_wrap() {
+    let adam = DebugObject::adam();
+    let apples = DebugObject::apples();
+    let mut out = WrapOutput::new(DebugOutput::new(), 22);
+
+    out.out().the(&adam).v(&adam, "take").the(&apples);
+    assert_eq!(out.inner().last_text, "Adam takes the apples.");
+
+    out.out()
+        .the(&adam)
+        .v(&adam, "give")
+        .the(&apples)
+        .s("to the old man in the big house");
+    assert_eq!(
+        out.inner().last_text,
+        "Adam gives the apples\nto the old man in the\nbig house."
+    );
+}
+
+#[test]
+fn test_no_wrap() {
+    let mut out = WrapOutput::new(DebugOutput::new(), 0);
+    out.write_text("a long line that is never wrapped");
+    out.done();
+    assert_eq!(out.inner().last_text, "a long line that is never wrapped");
+
+    out.set_width(10);
+    out.write_text("a verylongwordthatdoesnotfit here\nnew line");
+    out.done();
+    assert_eq!(
+        out.inner().last_text,
+        "a\nverylongwordthatdoesnotfit\nhere\nnew line"
+    );
+}
+
+#[test]
+fn test_unicode_width() {
+    let mut out = WrapOutput::new(DebugOutput::new(), 10);
+    out.write_text("åäö åäö 日本語 x");
+    out.done();
+    assert_eq!(out.inner().last_text, "åäö åäö\n日本語 x");
+}
+
+#[test]
+fn test_styles_are_not_measured() {
+    let adam = DebugObject::adam();
+    let mut out = WrapOutput::new(DebugOutput::new(), 14);
+
+    out.out()
+        .s("hello there")
+        .style("npc")
+        .the(&adam)
+        .end_style()
+        .s("and bye");
+    assert_eq!(out.inner().last_text, "Hello there\n<npc>Adam<> and bye.");
+
+    out.out()
+        .style("npc")
+        .the(&adam)
+        .end_style()
+        .s("says")
+        .style("speech")
+        .s("hi")
+        .end_style();
+    assert_eq!(out.inner().last_text, "<npc>Adam<> says <speech>hi<>.");
+}
+
+#[test]
+fn test_hanging_indent() {
+    let adam = DebugObject::adam();
+    let eva = DebugObject::eva();
+    let apple = DebugObject::apple();
+    let mut out = WrapOutput::new(DebugOutput::new(), 20).with_indent(2);
+
+    out.out()
+        .s("You see")
+        .a_list(&[&adam, &eva, &apple, &apple, &apple])
+        .s("here");
+    assert_eq!(
+        out.inner().last_text,
+        "You see Adam, Eva\n  and three apples\n  here."
+    );
+}
+
+#[test]
+fn test_forwards_to_inner() {
+    let adam = DebugObject::adam();
+    let mut inner = DebugOutput::new();
+    inner.me_id = Some(adam.id());
+    inner.tense = verb::Tense::Past;
+    let mut out = WrapOutput::new(inner, 80);
+
+    out.out().the(&adam).is(&adam).s("hungry");
+    assert_eq!(out.inner().last_text, "You were hungry.");
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_forwards_objects() {
+    use langgen::json::{Event, JsonOutput};
+
+    let adam = DebugObject::adam();
+    let apple = DebugObject::apple();
+    let mut out = WrapOutput::new(JsonOutput::new(vec![], DebugOutput::new()), 12);
+
+    out.out().the(&adam).v(&adam, "take").a_(&apple);
+    let text = String::from_utf8(out.into_inner().into_inner()).unwrap();
+    let event: Event = serde_json::from_str(&text).unwrap();
+    assert_eq!(event.text, "Adam takes a\ngreen apple.");
+    let mentions: Vec<(&str, Option<u64>)> = event
+        .segments
+        .iter()
+        .map(|s| (s.text.as_str(), s.object.map(|m| m.id)))
+        .collect();
+    assert_eq!(
+        mentions,
+        vec![
+            ("Adam", Some(adam.id())),
+            (" takes ", None),
+            ("a\ngreen apple", Some(apple.id())),
+            (".", None),
+        ]
+    );
+}